@@ -0,0 +1,8 @@
+-- A single row holding the number of visits
+CREATE TABLE IF NOT EXISTS visits
+(
+    id      INTEGER PRIMARY KEY CHECK (id = 1),
+    count   BIGINT  NOT NULL
+);
+
+INSERT INTO visits (id, count) VALUES (1, 0) ON CONFLICT DO NOTHING;
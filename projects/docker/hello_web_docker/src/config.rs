@@ -0,0 +1,61 @@
+use web_tls::env_var;
+use anyhow::{bail, Context};
+use figment::{
+    providers::{Env, Format, Serialized, Toml},
+    Figment,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+// Environment variables that override the matching setting
+const ENV_KEYS: [&str; 5] = ["bind_addr", "port", "log_level", "static_dir", "database_url"];
+
+/// Where the server listens and what it talks to. Each layer overrides the
+/// one before: built-in defaults, then `settings.toml` (or the file named by
+/// SETTINGS_FILE) if it exists, then environment variables named after the
+/// fields in capitals (BIND_ADDR, PORT, LOG_LEVEL, STATIC_DIR, DATABASE_URL).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Settings {
+    pub bind_addr: IpAddr,
+    pub port: u16,
+    /// Used when RUST_LOG isn't set
+    pub log_level: String,
+    /// Serve files from here for any path without a route
+    pub static_dir: Option<PathBuf>,
+    pub database_url: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            bind_addr: IpAddr::V4(Ipv4Addr::UNSPECIFIED),
+            port: 3001,
+            log_level: "info".to_string(),
+            static_dir: None,
+            database_url: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> anyhow::Result<Self> {
+        let file = env_var("SETTINGS_FILE").unwrap_or_else(|| "settings.toml".to_string());
+        let settings: Settings = Figment::from(Serialized::defaults(Settings::default()))
+            .merge(Toml::file(&file))
+            // Blank variables count as unset; some orchestrators pass them through
+            .merge(Env::raw().only(&ENV_KEYS).filter(|key| env_var(key.as_str()).is_some()))
+            .extract()
+            .with_context(|| format!("Invalid settings in {file} or the environment"))?;
+        if settings.port == 0 {
+            bail!("port must be from 1 to 65535");
+        }
+        Ok(settings)
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_addr, self.port)
+    }
+}
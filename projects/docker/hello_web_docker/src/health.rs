@@ -0,0 +1,87 @@
+use axum::{http::StatusCode, response::IntoResponse, Extension, Json};
+use serde::Serialize;
+use std::{future::Future, pin::Pin, sync::Arc, time::Duration};
+
+type CheckFuture = Pin<Box<dyn Future<Output = Result<(), String>> + Send>>;
+type Check = Box<dyn Fn() -> CheckFuture + Send + Sync>;
+
+/// A slow dependency counts as a failed one
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// The dependencies the server needs before it can take traffic. Each
+/// check returns `Err(reason)` when its dependency isn't usable.
+#[derive(Default)]
+pub struct Readiness {
+    checks: Vec<(&'static str, Check)>,
+}
+
+impl Readiness {
+    pub fn with_check<F, Fut>(mut self, name: &'static str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        self.checks.push((name, Box::new(move || Box::pin(check()))));
+        self
+    }
+
+    async fn failures(&self) -> Vec<Failure> {
+        let mut failures = Vec::new();
+        for (name, check) in &self.checks {
+            let reason = match tokio::time::timeout(CHECK_TIMEOUT, check()).await {
+                Ok(Ok(())) => continue,
+                Ok(Err(reason)) => reason,
+                Err(_) => format!("no answer within {CHECK_TIMEOUT:?}"),
+            };
+            failures.push(Failure {
+                dependency: name,
+                reason,
+            });
+        }
+        failures
+    }
+}
+
+#[derive(Serialize)]
+struct Failure {
+    dependency: &'static str,
+    reason: String,
+}
+
+#[derive(Serialize)]
+struct Status {
+    status: &'static str,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    failures: Vec<Failure>,
+}
+
+/// Liveness: the process is up and answering requests.
+pub async fn healthz() -> impl IntoResponse {
+    Json(Status {
+        status: "ok",
+        failures: Vec::new(),
+    })
+}
+
+/// Readiness: every declared dependency is usable. Otherwise 503, with
+/// the reasons in the body.
+pub async fn readyz(Extension(readiness): Extension<Arc<Readiness>>) -> impl IntoResponse {
+    let failures = readiness.failures().await;
+    if failures.is_empty() {
+        (
+            StatusCode::OK,
+            Json(Status {
+                status: "ready",
+                failures,
+            }),
+        )
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(Status {
+                status: "not ready",
+                failures,
+            }),
+        )
+    }
+}
@@ -1,16 +1,133 @@
-use axum::{routing::get, Router};
-use std::{net::SocketAddr, path::Path};
+use anyhow::Context;
+use axum::{routing::get, Extension, Router};
+use std::{
+    net::SocketAddr,
+    sync::Arc,
+    time::Duration,
+};
 use axum::response::Html;
+use tower::ServiceBuilder;
+use tower_http::{
+    services::ServeDir,
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnResponse, TraceLayer},
+    LatencyUnit,
+};
+use tracing::Level;
+
+mod config;
+mod health;
+mod shutdown;
+mod visits;
 
 #[tokio::main]
-async fn main() {
-    let app = Router::new()
-        .route("/", get(say_hello_html));
-    let addr = SocketAddr::from(([0, 0, 0, 0], 3001));    
-    axum::Server::bind(&addr)
+async fn main() -> anyhow::Result<()> {
+    let settings = config::Settings::load()?;
+    telemetry::init(&settings.log_level);
+    let addr = settings.addr();
+    let tls = web_tls::TlsSettings::from_env()?;
+    let drain_timeout = shutdown::drain_timeout()?;
+
+    let mut readiness = health::Readiness::default();
+    let mut app = Router::new()
+        .route("/", get(say_hello_html))
+        .route("/healthz", get(health::healthz))
+        .route("/readyz", get(health::readyz));
+
+    // The database is optional; without it there's no /visits
+    if let Some(database_url) = &settings.database_url {
+        let pool = visits::connect(database_url).await?;
+        let check_pool = pool.clone();
+        readiness = readiness.with_check("database", move || visits::ping(check_pool.clone()));
+        app = app
+            .route("/visits", get(visits::visits))
+            .layer(Extension(pool));
+    }
+    if let Some(static_dir) = &settings.static_dir {
+        app = app.fallback_service(ServeDir::new(static_dir));
+    }
+
+    let app = app
+        .layer(Extension(Arc::new(readiness)))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(telemetry::make_span)
+                        .on_response(
+                            DefaultOnResponse::new()
+                                .level(Level::INFO)
+                                .latency_unit(LatencyUnit::Millis),
+                        ),
+                )
+                .layer(PropagateRequestIdLayer::x_request_id()),
+        );
+    let shutdown = shutdown::listen();
+    match tls {
+        Some(tls) => serve_https(app, addr, tls, shutdown, drain_timeout).await,
+        None => serve_http(app, addr, shutdown, drain_timeout).await,
+    }
+}
+
+async fn serve_http(
+    app: Router,
+    addr: SocketAddr,
+    shutdown: shutdown::ShutdownReceiver,
+    drain_timeout: Duration,
+) -> anyhow::Result<()> {
+    tracing::info!("Listening on http://{addr}");
+    let server = axum::Server::try_bind(&addr)
+        .with_context(|| format!("Unable to listen on {addr}"))?
+        .serve(app.into_make_service())
+        .with_graceful_shutdown(shutdown::requested(shutdown.clone()));
+    shutdown::drain(
+        async move {
+            server.await?;
+            Ok(())
+        },
+        shutdown,
+        drain_timeout,
+    )
+    .await
+}
+
+async fn serve_https(
+    app: Router,
+    addr: SocketAddr,
+    tls: web_tls::TlsSettings,
+    shutdown: shutdown::ShutdownReceiver,
+    drain_timeout: Duration,
+) -> anyhow::Result<()> {
+    let config = tls.rustls_config().await?;
+    if tls.redirect_http {
+        let redirect =
+            web_tls::redirect_server(addr, tls.port, shutdown::requested(shutdown.clone()))?;
+        tracing::info!("Redirecting http://{addr} to HTTPS");
+        tokio::spawn(async move {
+            if let Err(e) = redirect.await {
+                tracing::error!("HTTP redirect server failed: {e}");
+            }
+        });
+    }
+
+    let https_addr = SocketAddr::new(addr.ip(), tls.port);
+    tracing::info!("Listening on https://{https_addr}");
+    let handle = axum_server::Handle::new();
+    tokio::spawn({
+        let handle = handle.clone();
+        let shutdown = shutdown.clone();
+        async move {
+            shutdown::requested(shutdown).await;
+            handle.graceful_shutdown(Some(drain_timeout));
+        }
+    });
+    axum_server::bind_rustls(https_addr, config)
+        .handle(handle)
         .serve(app.into_make_service())
         .await
-        .unwrap();
+        .with_context(|| format!("Unable to serve HTTPS on {https_addr}"))?;
+    Ok(())
 }
 
 async fn say_hello_html() -> Html<&'static str> {
@@ -0,0 +1,86 @@
+use web_tls::env_var;
+use anyhow::Context;
+use std::{future::Future, time::Duration};
+use tokio::sync::watch;
+
+/// Changes to `true` once the server has been asked to stop.
+pub type ShutdownReceiver = watch::Receiver<bool>;
+
+// Docker sends SIGKILL 10 seconds after SIGTERM, so finish before that
+const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(8);
+
+/// How long to let in-flight requests finish after a shutdown signal,
+/// from SHUTDOWN_TIMEOUT_SECS.
+pub fn drain_timeout() -> anyhow::Result<Duration> {
+    match env_var("SHUTDOWN_TIMEOUT_SECS") {
+        Some(secs) => {
+            let secs: u64 = secs
+                .parse()
+                .with_context(|| format!("SHUTDOWN_TIMEOUT_SECS `{secs}` is not a number of seconds"))?;
+            Ok(Duration::from_secs(secs))
+        }
+        None => Ok(DEFAULT_DRAIN_TIMEOUT),
+    }
+}
+
+/// Starts listening for Ctrl+C and SIGTERM. Every listener gets a clone of
+/// the receiver, so one signal stops them all.
+pub fn listen() -> ShutdownReceiver {
+    let (tx, rx) = watch::channel(false);
+    tokio::spawn(async move {
+        signal().await;
+        tracing::info!("Shutting down");
+        let _ = tx.send(true);
+    });
+    rx
+}
+
+async fn signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Unable to listen for Ctrl+C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Unable to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+}
+
+/// Resolves once shutdown has been requested.
+pub async fn requested(mut shutdown: ShutdownReceiver) {
+    // An error means the sender is gone, which only happens on the way out
+    let _ = shutdown.wait_for(|stop| *stop).await;
+}
+
+/// Runs a gracefully-stopping server, but gives up on stragglers once
+/// `limit` has passed since the shutdown request.
+pub async fn drain<F>(server: F, shutdown: ShutdownReceiver, limit: Duration) -> anyhow::Result<()>
+where
+    F: Future<Output = anyhow::Result<()>>,
+{
+    tokio::pin!(server);
+    tokio::select! {
+        result = &mut server => return result,
+        _ = requested(shutdown) => {}
+    }
+    match tokio::time::timeout(limit, server).await {
+        Ok(result) => result,
+        Err(_) => {
+            tracing::warn!("Requests still running after {limit:?}; stopping anyway");
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use anyhow::Context;
+use axum::{http::StatusCode, Extension, Json};
+use serde::Serialize;
+use sqlx::{postgres::PgPoolOptions, PgPool};
+use std::time::Duration;
+
+/// Connects to Postgres and brings the schema up to date.
+pub async fn connect(database_url: &str) -> anyhow::Result<PgPool> {
+    let pool = PgPoolOptions::new()
+        .max_connections(5)
+        .acquire_timeout(Duration::from_secs(3))
+        .connect(database_url)
+        .await
+        .context("Unable to connect to DATABASE_URL")?;
+
+    // The migrations are compiled into the binary
+    sqlx::migrate!("./migrations")
+        .run(&pool)
+        .await
+        .context("Unable to run database migrations")?;
+    Ok(pool)
+}
+
+/// Readiness check: can we still run a query?
+pub async fn ping(pool: PgPool) -> Result<(), String> {
+    sqlx::query("SELECT 1")
+        .execute(&pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Serialize)]
+pub struct Visits {
+    visits: i64,
+}
+
+/// Counts this visit and returns the total so far.
+pub async fn visits(Extension(pool): Extension<PgPool>) -> Result<Json<Visits>, StatusCode> {
+    // One statement, so concurrent visits can't lose an update
+    let visits: i64 = sqlx::query_scalar("UPDATE visits SET count = count + 1 WHERE id = 1 RETURNING count")
+        .fetch_one(&pool)
+        .await
+        .map_err(|e| {
+            tracing::error!("Unable to count visit: {e}");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    Ok(Json(Visits { visits }))
+}
@@ -0,0 +1,7 @@
+fn main() {
+    println!("cargo:rerun-if-changed=src/greeting.c");
+    println!("cargo:rerun-if-changed=src/greeting.h");
+    cc::Build::new()
+        .file("src/greeting.c")
+        .compile("greeting");
+}
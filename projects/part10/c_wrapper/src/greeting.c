@@ -0,0 +1,54 @@
+#include "greeting.h"
+#include <ctype.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+int greet(const char *name, char *buffer, size_t buffer_len) {
+    if (name == NULL) {
+        return -1;
+    }
+    return snprintf(buffer, buffer_len, "Hello, %s!", name);
+}
+
+int parse_celsius(const char *text, double *out) {
+    char *end;
+    double value = strtod(text, &end);
+    if (end == text || *end != '\0') {
+        return 1;
+    }
+    if (value < -273.15) {
+        return 2;
+    }
+    *out = value;
+    return 0;
+}
+
+char *shout(const char *text) {
+    if (text == NULL) {
+        return NULL;
+    }
+    size_t len = strlen(text);
+    char *result = malloc(len + 1);
+    if (result == NULL) {
+        return NULL;
+    }
+    for (size_t i = 0; i < len; i++) {
+        result[i] = (char)toupper((unsigned char)text[i]);
+    }
+    result[len] = '\0';
+    return result;
+}
+
+void free_string(char *text) {
+    free(text);
+}
+
+uint32_t fnv1a(const uint8_t *data, size_t len) {
+    uint32_t hash = 2166136261u;
+    for (size_t i = 0; i < len; i++) {
+        hash ^= data[i];
+        hash *= 16777619u;
+    }
+    return hash;
+}
@@ -0,0 +1,18 @@
+#include <stddef.h>
+#include <stdint.h>
+
+// Writes "Hello, <name>!" into buffer, like snprintf. Returns the length the
+// whole greeting needs (without the NUL), or -1 if name is NULL.
+int greet(const char *name, char *buffer, size_t buffer_len);
+
+// Returns 0 and sets *out on success, 1 if text isn't a number, and 2 if
+// it's below absolute zero.
+int parse_celsius(const char *text, double *out);
+
+// Returns an upper-case copy of text, allocated with malloc - release it
+// with free_string. Returns NULL if text is NULL or allocation fails.
+char *shout(const char *text);
+void free_string(char *text);
+
+// 32-bit FNV-1a hash of len bytes
+uint32_t fnv1a(const uint8_t *data, size_t len);
@@ -0,0 +1,145 @@
+//! A safe Rust API over the C functions in `greeting.c`. Everything unsafe
+//! stays in this file: callers get `&str` in, `String` and `Result` out.
+
+use std::ffi::{c_char, c_int, CStr, CString, NulError};
+use thiserror::Error;
+
+// Declared by hand, to match greeting.h. Nothing checks that these are
+// right - get a type wrong and you get undefined behaviour, not an error.
+mod ffi {
+    use std::ffi::{c_char, c_int};
+
+    extern "C" {
+        pub fn greet(name: *const c_char, buffer: *mut c_char, buffer_len: usize) -> c_int;
+        pub fn parse_celsius(text: *const c_char, out: *mut f64) -> c_int;
+        pub fn shout(text: *const c_char) -> *mut c_char;
+        pub fn free_string(text: *mut c_char);
+        pub fn fnv1a(data: *const u8, len: usize) -> u32;
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CError {
+    // C strings end at the first NUL, so one in the middle would silently
+    // cut the string short
+    #[error("the string contains a NUL byte at position {0}")]
+    InteriorNul(usize),
+    #[error("C returned an unexpected code: {0}")]
+    Unexpected(c_int),
+    #[error("not a number")]
+    NotANumber,
+    #[error("below absolute zero")]
+    BelowAbsoluteZero,
+    #[error("C was unable to allocate memory")]
+    OutOfMemory,
+    #[error("C returned text that isn't UTF-8")]
+    NotUtf8,
+}
+
+impl From<NulError> for CError {
+    fn from(e: NulError) -> Self {
+        CError::InteriorNul(e.nul_position())
+    }
+}
+
+fn into_string(text: &CStr) -> Result<String, CError> {
+    text.to_str()
+        .map(str::to_string)
+        .map_err(|_| CError::NotUtf8)
+}
+
+pub fn greet(name: &str) -> Result<String, CError> {
+    // The CString has to live until C is done with the pointer
+    let name = CString::new(name)?;
+
+    // Ask how much room it needs, then call again with a big enough buffer
+    let needed = unsafe { ffi::greet(name.as_ptr(), std::ptr::null_mut(), 0) };
+    if needed < 0 {
+        return Err(CError::Unexpected(needed));
+    }
+    let mut buffer = vec![0u8; needed as usize + 1];
+    let written = unsafe {
+        ffi::greet(name.as_ptr(), buffer.as_mut_ptr() as *mut c_char, buffer.len())
+    };
+    if written != needed {
+        return Err(CError::Unexpected(written));
+    }
+
+    let greeting = CStr::from_bytes_with_nul(&buffer).map_err(|_| CError::Unexpected(written))?;
+    into_string(greeting)
+}
+
+pub fn parse_celsius(text: &str) -> Result<f64, CError> {
+    let text = CString::new(text)?;
+    let mut value = 0.0;
+    match unsafe { ffi::parse_celsius(text.as_ptr(), &mut value) } {
+        0 => Ok(value),
+        1 => Err(CError::NotANumber),
+        2 => Err(CError::BelowAbsoluteZero),
+        code => Err(CError::Unexpected(code)),
+    }
+}
+
+pub fn shout(text: &str) -> Result<String, CError> {
+    let text = CString::new(text)?;
+    let result = unsafe { ffi::shout(text.as_ptr()) };
+    if result.is_null() {
+        return Err(CError::OutOfMemory);
+    }
+    // C allocated it, so C has to free it - after we've copied it into a
+    // Rust String, and even if the copy fails
+    let copied = into_string(unsafe { CStr::from_ptr(result) });
+    unsafe { ffi::free_string(result) };
+    copied
+}
+
+/// No strings involved, so no conversion: a slice is already a pointer and
+/// a length.
+pub fn fnv1a(data: &[u8]) -> u32 {
+    unsafe { ffi::fnv1a(data.as_ptr(), data.len()) }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn greets() {
+        assert_eq!(greet("Herbert").unwrap(), "Hello, Herbert!");
+        assert_eq!(greet("").unwrap(), "Hello, !");
+    }
+
+    #[test]
+    fn long_names_fit() {
+        let name = "x".repeat(1000);
+        assert_eq!(greet(&name).unwrap().len(), "Hello, !".len() + 1000);
+    }
+
+    #[test]
+    fn interior_nul_is_an_error() {
+        assert_eq!(greet("Her\0bert"), Err(CError::InteriorNul(3)));
+        assert_eq!(shout("a\0"), Err(CError::InteriorNul(1)));
+    }
+
+    #[test]
+    fn parses_celsius() {
+        assert_eq!(parse_celsius("21.5"), Ok(21.5));
+        assert_eq!(parse_celsius("-273.15"), Ok(-273.15));
+        assert_eq!(parse_celsius("warm"), Err(CError::NotANumber));
+        assert_eq!(parse_celsius("21.5C"), Err(CError::NotANumber));
+        assert_eq!(parse_celsius(""), Err(CError::NotANumber));
+        assert_eq!(parse_celsius("-300"), Err(CError::BelowAbsoluteZero));
+    }
+
+    #[test]
+    fn shouts() {
+        assert_eq!(shout("hello, world").unwrap(), "HELLO, WORLD");
+    }
+
+    #[test]
+    fn hashes_match_reference_values() {
+        assert_eq!(fnv1a(b""), 0x811c9dc5);
+        assert_eq!(fnv1a(b"a"), 0xe40c292c);
+        assert_eq!(fnv1a(b"foobar"), 0xbf9cf968);
+    }
+}
@@ -0,0 +1,39 @@
+use allocations::*;
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const NODES: u64 = 10_000;
+
+fn lists(c: &mut Criterion) {
+    let (_, boxed) = count_allocations(|| BoxedList::build(NODES));
+    let (_, arena) = count_allocations(|| ArenaList::build(NODES));
+    println!("Building {NODES} nodes: Box {boxed} allocations, arena {arena}");
+
+    let mut group = c.benchmark_group("build and sum a list");
+    group.bench_function("Box per node", |b| {
+        b.iter(|| BoxedList::build(black_box(NODES)).sum())
+    });
+    group.bench_function("arena", |b| {
+        b.iter(|| ArenaList::build(black_box(NODES)).sum())
+    });
+    group.finish();
+}
+
+fn vecs(c: &mut Criterion) {
+    let n = NODES as usize;
+    let (_, growing) = count_allocations(|| fill_vec(n));
+    let (_, reserved) = count_allocations(|| fill_vec_with_capacity(n));
+    println!("Filling a Vec with {n} items: {growing} allocations growing, {reserved} reserved");
+
+    let mut group = c.benchmark_group("fill a Vec");
+    group.bench_function("Vec::new", |b| b.iter(|| fill_vec(black_box(n))));
+    group.bench_function("Vec::with_capacity", |b| {
+        b.iter(|| fill_vec_with_capacity(black_box(n)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lists, vecs);
+criterion_main!(benches);
@@ -0,0 +1,175 @@
+mod counting;
+
+pub use counting::{count_allocations, CountingAllocator};
+
+/// The classic linked list: every node is its own heap allocation,
+/// wherever the allocator found room for it.
+pub struct BoxedList {
+    head: Option<Box<BoxNode>>,
+}
+
+struct BoxNode {
+    value: u64,
+    next: Option<Box<BoxNode>>,
+}
+
+impl BoxedList {
+    pub fn build(n: u64) -> Self {
+        let mut head = None;
+        for value in (0..n).rev() {
+            head = Some(Box::new(BoxNode { value, next: head }));
+        }
+        Self { head }
+    }
+
+    pub fn sum(&self) -> u64 {
+        let mut total = 0;
+        let mut node = &self.head;
+        while let Some(current) = node {
+            total += current.value;
+            node = &current.next;
+        }
+        total
+    }
+}
+
+// The default drop is recursive - each Box drops the next - so a long
+// enough list overflows the stack. Unlink the nodes one at a time instead.
+impl Drop for BoxedList {
+    fn drop(&mut self) {
+        let mut node = self.head.take();
+        while let Some(mut current) = node {
+            node = current.next.take();
+        }
+    }
+}
+
+/// The same list in an arena: the nodes live side by side in one Vec and
+/// link to each other by index. One allocation for the lot, and they're
+/// all freed together when the arena goes.
+pub struct ArenaList {
+    nodes: Vec<ArenaNode>,
+    head: Option<usize>,
+}
+
+struct ArenaNode {
+    value: u64,
+    next: Option<usize>,
+}
+
+impl ArenaList {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+            head: None,
+        }
+    }
+
+    /// Pushes onto the front, returning the new node's index
+    pub fn push_front(&mut self, value: u64) -> usize {
+        self.nodes.push(ArenaNode {
+            value,
+            next: self.head,
+        });
+        let index = self.nodes.len() - 1;
+        self.head = Some(index);
+        index
+    }
+
+    pub fn build(n: u64) -> Self {
+        let mut list = Self::with_capacity(n as usize);
+        for value in (0..n).rev() {
+            list.push_front(value);
+        }
+        list
+    }
+
+    pub fn sum(&self) -> u64 {
+        let mut total = 0;
+        let mut index = self.head;
+        while let Some(i) = index {
+            total += self.nodes[i].value;
+            index = self.nodes[i].next;
+        }
+        total
+    }
+}
+
+/// Pushes `n` values onto an empty Vec, recording the capacity each time
+/// it changes. Every change is a reallocation.
+pub fn vec_growth(n: usize) -> Vec<usize> {
+    let mut v = Vec::new();
+    let mut capacities = vec![v.capacity()];
+    for i in 0..n {
+        v.push(i);
+        if v.capacity() != *capacities.last().unwrap() {
+            capacities.push(v.capacity());
+        }
+    }
+    capacities
+}
+
+pub fn fill_vec(n: usize) -> Vec<usize> {
+    let mut v = Vec::new();
+    for i in 0..n {
+        v.push(i);
+    }
+    v
+}
+
+pub fn fill_vec_with_capacity(n: usize) -> Vec<usize> {
+    let mut v = Vec::with_capacity(n);
+    for i in 0..n {
+        v.push(i);
+    }
+    v
+}
+
+#[cfg(test)]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn lists_agree() {
+        assert_eq!(BoxedList::build(1000).sum(), ArenaList::build(1000).sum());
+        assert_eq!(ArenaList::build(1000).sum(), (0..1000).sum());
+    }
+
+    #[test]
+    fn long_boxed_list_drops_without_overflowing() {
+        drop(BoxedList::build(1_000_000));
+    }
+
+    #[test]
+    fn boxed_list_allocates_per_node() {
+        let (_list, allocations) = count_allocations(|| BoxedList::build(1000));
+        assert_eq!(allocations, 1000);
+    }
+
+    #[test]
+    fn arena_list_allocates_once() {
+        let (_list, allocations) = count_allocations(|| ArenaList::build(1000));
+        assert_eq!(allocations, 1);
+    }
+
+    #[test]
+    fn vec_grows_by_doubling() {
+        let capacities = vec_growth(100);
+        assert_eq!(capacities[0], 0);
+        for pair in capacities[1..].windows(2) {
+            assert_eq!(pair[1], pair[0] * 2);
+        }
+    }
+
+    #[test]
+    fn capacity_avoids_reallocation() {
+        let (_, growing) = count_allocations(|| fill_vec(10_000));
+        let (_, reserved) = count_allocations(|| fill_vec_with_capacity(10_000));
+        assert!(growing > 1);
+        assert_eq!(reserved, 1);
+    }
+}
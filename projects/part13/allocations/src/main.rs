@@ -0,0 +1,27 @@
+use allocations::*;
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const NODES: u64 = 100_000;
+
+fn main() {
+    println!("A Box is one allocation:");
+    let (_, n) = count_allocations(|| Box::new(42));
+    println!("  Box::new(42): {n} allocation\n");
+
+    println!("A Vec reallocates as it grows, doubling its capacity each time:");
+    let capacities = vec_growth(1000);
+    println!("  Capacities pushing 1000 items: {capacities:?}");
+    let (_, growing) = count_allocations(|| fill_vec(NODES as usize));
+    let (_, reserved) = count_allocations(|| fill_vec_with_capacity(NODES as usize));
+    println!("  {NODES} pushes onto Vec::new(): {growing} allocations");
+    println!("  {NODES} pushes onto Vec::with_capacity(): {reserved} allocation\n");
+
+    println!("A {NODES} node linked list:");
+    let (boxed, boxed_allocations) = count_allocations(|| BoxedList::build(NODES));
+    let (arena, arena_allocations) = count_allocations(|| ArenaList::build(NODES));
+    println!("  Box per node: {boxed_allocations} allocations, sum {}", boxed.sum());
+    println!("  In an arena:  {arena_allocations} allocation, sum {}", arena.sum());
+    println!("\nRun `cargo bench` to see what that does to the time.");
+}
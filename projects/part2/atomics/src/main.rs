@@ -1,3 +1,6 @@
+mod spinlock;
+
+use spinlock::SpinLock;
 use std::sync::atomic::Ordering::Relaxed;
 use std::sync::atomic::AtomicU32;
 
@@ -18,4 +21,34 @@ fn main() {
         let _ = t2.join(); // let _ means "ignore" - we're ignoring the result type
     });
     println!("{}", counter.load(Relaxed));
-}
\ No newline at end of file
+
+    spin_counter();
+}
+
+// Run with `cargo run -- --relaxed` to try the broken spinlock
+fn spin_counter() {
+    let relaxed = std::env::args().any(|arg| arg == "--relaxed");
+    let counter = if relaxed {
+        println!("Spinlock with Relaxed ordering (broken!)");
+        // Safety: it isn't - the lock is shared below, and the lost
+        // increments are what this demonstrates
+        unsafe { SpinLock::new_relaxed(0) }
+    } else {
+        println!("Spinlock with Acquire/Release ordering");
+        SpinLock::new(0)
+    };
+
+    std::thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                for _ in 0 .. 1000000 {
+                    *counter.lock() += 1;
+                }
+            });
+        }
+    });
+    // Relaxed can lose increments on weakly ordered CPUs such as ARM. x86
+    // orders its loads and stores more strongly, so it often gets lucky -
+    // but the compiler is still allowed to break it.
+    println!("{} (expected 2000000)", counter.into_inner());
+}
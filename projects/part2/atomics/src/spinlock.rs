@@ -0,0 +1,87 @@
+use std::cell::UnsafeCell;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A lock that spins until it gets its way, instead of asking the OS to
+/// put the thread to sleep.
+pub struct SpinLock<T> {
+    locked: AtomicBool,
+    value: UnsafeCell<T>,
+    acquire: Ordering,
+    release: Ordering,
+}
+
+// The lock hands out one &mut T at a time, so sharing it between threads
+// is fine as long as T itself can be sent between them.
+unsafe impl<T: Send> Sync for SpinLock<T> {}
+
+impl<T> SpinLock<T> {
+    pub fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+            acquire: Ordering::Acquire,
+            release: Ordering::Release,
+        }
+    }
+
+    /// The same lock with every ordering relaxed. Taking the lock no longer
+    /// guarantees you see what the last holder wrote, so this is broken -
+    /// it's only here to demonstrate why.
+    ///
+    /// # Safety
+    ///
+    /// Two threads that take the lock in turn can still access the value at
+    /// the same time as far as the memory model is concerned, which is a
+    /// data race. Only sound if the lock is never shared between threads.
+    pub unsafe fn new_relaxed(value: T) -> Self {
+        Self {
+            acquire: Ordering::Relaxed,
+            release: Ordering::Relaxed,
+            ..Self::new(value)
+        }
+    }
+
+    pub fn lock(&self) -> SpinLockGuard<'_, T> {
+        // Acquire pairs with the Release in unlock: everything the previous
+        // holder did before unlocking is visible once we get in
+        while self
+            .locked
+            .compare_exchange_weak(false, true, self.acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            std::hint::spin_loop();
+        }
+        SpinLockGuard { lock: self }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+pub struct SpinLockGuard<'a, T> {
+    lock: &'a SpinLock<T>,
+}
+
+impl<T> Deref for SpinLockGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // Safety: holding the guard means we hold the lock
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for SpinLockGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: holding the guard means we hold the lock
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
+
+impl<T> Drop for SpinLockGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.locked.store(false, self.lock.release);
+    }
+}
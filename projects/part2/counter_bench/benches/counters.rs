@@ -0,0 +1,23 @@
+use counter_bench::{STRATEGIES, THREAD_COUNTS};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const PER_THREAD: u64 = 100_000;
+
+fn counters(c: &mut Criterion) {
+    // One group per thread count, so each report compares the counters
+    // side by side
+    for threads in THREAD_COUNTS {
+        let mut group = c.benchmark_group(format!("{threads} threads"));
+        group.throughput(Throughput::Elements(threads as u64 * PER_THREAD));
+        for (name, strategy) in STRATEGIES {
+            group.bench_with_input(BenchmarkId::from_parameter(name), &threads, |b, &threads| {
+                // Safety: as in main.rs, unsafe_static races on purpose
+                b.iter(|| unsafe { strategy(threads, PER_THREAD) })
+            });
+        }
+        group.finish();
+    }
+}
+
+criterion_group!(benches, counters);
+criterion_main!(benches);
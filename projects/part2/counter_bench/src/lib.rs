@@ -0,0 +1,112 @@
+//! The counters from the `mutex`, `atomics` and `unsafe_threading`
+//! projects, plus one that doesn't share anything until the end. Each
+//! spawns `threads` threads that add 1 `per_thread` times, and returns the
+//! final count.
+
+use std::hint::black_box;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+
+pub const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+
+/// `unsafe` because of `unsafe_static`; the other strategies are safe.
+pub type Strategy = unsafe fn(usize, u64) -> u64;
+
+pub const STRATEGIES: [(&str, Strategy); 4] = [
+    ("mutex", mutex),
+    ("atomic", atomic),
+    ("unsafe static", unsafe_static),
+    ("per-thread sum", per_thread_sum),
+];
+
+pub fn mutex(threads: usize, per_thread: u64) -> u64 {
+    let counter = Mutex::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    *counter.lock().unwrap() += 1;
+                }
+            });
+        }
+    });
+    counter.into_inner().unwrap()
+}
+
+pub fn atomic(threads: usize, per_thread: u64) -> u64 {
+    let counter = AtomicU64::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    counter.into_inner()
+}
+
+/// A data race: undefined behaviour, and with more than one thread the
+/// total usually comes out short. It's here to show what the safe versions
+/// cost, not as something to copy.
+///
+/// # Safety
+///
+/// Only sound with `threads == 1`, and while no other call is running.
+/// Anything more is a data race on the shared counter.
+pub unsafe fn unsafe_static(threads: usize, per_thread: u64) -> u64 {
+    static mut COUNTER: u64 = 0;
+    unsafe {
+        std::ptr::addr_of_mut!(COUNTER).write(0);
+    }
+    std::thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..per_thread {
+                    // Volatile stops the compiler turning the loop into a
+                    // single `+= per_thread`, which would hide the race
+                    unsafe {
+                        let counter = std::ptr::addr_of_mut!(COUNTER);
+                        counter.write_volatile(counter.read_volatile() + 1);
+                    }
+                }
+            });
+        }
+    });
+    unsafe { std::ptr::addr_of!(COUNTER).read() }
+}
+
+/// Each thread counts on its own and the totals are added up at the end,
+/// so there's nothing to contend over.
+pub fn per_thread_sum(threads: usize, per_thread: u64) -> u64 {
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut count = 0;
+                    for _ in 0..per_thread {
+                        // black_box keeps the loop honest, as volatile does above
+                        count = black_box(count + 1);
+                    }
+                    count
+                })
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn safe_counters_are_exact() {
+        for threads in THREAD_COUNTS {
+            assert_eq!(mutex(threads, 1000), threads as u64 * 1000);
+            assert_eq!(atomic(threads, 1000), threads as u64 * 1000);
+            assert_eq!(per_thread_sum(threads, 1000), threads as u64 * 1000);
+        }
+    }
+}
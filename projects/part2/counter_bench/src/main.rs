@@ -0,0 +1,42 @@
+use counter_bench::{STRATEGIES, THREAD_COUNTS};
+use std::time::{Duration, Instant};
+
+const PER_THREAD: u64 = 1_000_000;
+const RUNS: usize = 5;
+
+// For a quick answer; `cargo bench` gives the careful one
+fn main() {
+    print!("| {:<16}|", "counter");
+    for threads in THREAD_COUNTS {
+        print!(" {:>9} |", format!("{threads} thread{}", if threads == 1 { "" } else { "s" }));
+    }
+    println!();
+    print!("|{:-<17}|", "");
+    for _ in THREAD_COUNTS {
+        print!("{:-<11}|", "");
+    }
+    println!();
+
+    for (name, strategy) in STRATEGIES {
+        print!("| {name:<16}|");
+        let mut wrong_total = false;
+        for threads in THREAD_COUNTS {
+            // Best of a few runs, to skip past any that got unlucky scheduling
+            let mut best = Duration::MAX;
+            for _ in 0..RUNS {
+                let start = Instant::now();
+                // Safety: unsafe_static breaks its contract with more than
+                // one thread, and that's the race this table shows
+                let total = unsafe { strategy(threads, PER_THREAD) };
+                best = best.min(start.elapsed());
+                wrong_total |= total != threads as u64 * PER_THREAD;
+            }
+            print!(" {:>9} |", format!("{:.1?}", best));
+        }
+        if wrong_total {
+            print!(" wrong total!");
+        }
+        println!();
+    }
+    println!("\nEach thread adds 1 to the counter {PER_THREAD} times.");
+}
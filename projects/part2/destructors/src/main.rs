@@ -1,3 +1,7 @@
+mod transaction;
+
+use transaction::{Account, Transaction};
+
 struct MyStruct {
     s: String
 }
@@ -8,7 +12,7 @@ impl Drop for MyStruct {
     }
 }
 
-fn do_it(a: MyStruct) {
+fn do_it(_a: MyStruct) {
     println!("do_it called");
 }
 
@@ -25,4 +29,16 @@ fn main() {
     let b = MyStruct { s: "2".to_string() };
     let b = move_it(b);
     println!("{}", b.s);
+
+    let mut account = Account { balance: 100 };
+    let mut tx = Transaction::begin(&mut account);
+    tx.deposit(25);
+    tx.commit();
+
+    let mut tx = Transaction::begin(&mut account);
+    if let Err(e) = tx.withdraw(1000) {
+        println!("{e}");
+    }
+    drop(tx); // Never committed
+    println!("Balance: {}", account.balance);
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+/// A bank account, to give the transaction something to change.
+pub struct Account {
+    pub balance: i64,
+}
+
+/// RAII: the guard remembers the balance when it's created, and puts it back
+/// when it's dropped - unless you `commit()` first. Returning early, `?` and
+/// panicking all drop the guard, so none of them can leave a half-finished
+/// transaction behind.
+pub struct Transaction<'a> {
+    account: &'a mut Account,
+    original: i64,
+    committed: bool,
+}
+
+impl<'a> Transaction<'a> {
+    pub fn begin(account: &'a mut Account) -> Self {
+        println!("Transaction started, balance {}", account.balance);
+        let original = account.balance;
+        Self {
+            account,
+            original,
+            committed: false,
+        }
+    }
+
+    pub fn deposit(&mut self, amount: i64) {
+        self.account.balance += amount;
+    }
+
+    pub fn withdraw(&mut self, amount: i64) -> Result<(), String> {
+        if self.account.balance - amount < 0 {
+            return Err(format!("Insufficient funds to withdraw {amount}"));
+        }
+        self.account.balance -= amount;
+        Ok(())
+    }
+
+    /// Takes `self`, so the transaction can't be used after it's committed.
+    /// Drop still runs, but has nothing left to undo.
+    pub fn commit(mut self) {
+        self.committed = true;
+        println!("Committed, balance {}", self.account.balance);
+    }
+}
+
+impl Drop for Transaction<'_> {
+    fn drop(&mut self) {
+        if !self.committed {
+            self.account.balance = self.original;
+            if std::thread::panicking() {
+                println!("Panicked - rolled back to {}", self.original);
+            } else {
+                println!("Not committed - rolled back to {}", self.original);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn commit_keeps_changes() {
+        let mut account = Account { balance: 100 };
+        let mut tx = Transaction::begin(&mut account);
+        tx.deposit(50);
+        tx.commit();
+        assert_eq!(account.balance, 150);
+    }
+
+    #[test]
+    fn error_rolls_back() {
+        fn pay(account: &mut Account) -> Result<(), String> {
+            let mut tx = Transaction::begin(account);
+            tx.deposit(10);
+            tx.withdraw(500)?;
+            tx.commit();
+            Ok(())
+        }
+
+        let mut account = Account { balance: 100 };
+        assert!(pay(&mut account).is_err());
+        assert_eq!(account.balance, 100);
+    }
+
+    #[test]
+    fn failed_withdrawal_leaves_balance_alone() {
+        let mut account = Account { balance: 100 };
+        let mut tx = Transaction::begin(&mut account);
+        assert!(tx.withdraw(500).is_err());
+        tx.withdraw(30).unwrap();
+        tx.commit();
+        assert_eq!(account.balance, 70);
+    }
+
+    #[test]
+    fn panic_rolls_back() {
+        let mut account = Account { balance: 100 };
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let mut tx = Transaction::begin(&mut account);
+            tx.deposit(50);
+            panic!("Something went wrong mid-transaction");
+        }));
+        assert!(result.is_err());
+        assert_eq!(account.balance, 100);
+    }
+}
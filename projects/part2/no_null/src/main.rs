@@ -1,13 +1,15 @@
+use no_null::Config;
+
 fn frobnicator() -> Option<i32> {
     Some(3)
 }
 
 fn main() {
     // Panic if the option is equal to None
-    let a = frobnicator().unwrap();
+    let _a = frobnicator().unwrap();
     
     // Panic with a nice error message if the option is equal to None
-    let a = frobnicator().expect("The frobnicator is broken!");
+    let _a = frobnicator().expect("The frobnicator is broken!");
 
     // Just check to see if we got a value at all
     let a = frobnicator();
@@ -24,15 +26,25 @@ fn main() {
         None => {
             // Do Something
         }
-        Some(a) => {
-            // a now refers to the contents of a
+        Some(_a) => {
+            // _a now refers to the contents of a
             // Do something
         }
     }
 
     // Use "if let" for single-arm pattern matching
     let a = frobnicator();
-    if let Some(a) = a {
+    if let Some(_a) = a {
         // Do something
     }
+
+    // The combinators in lib.rs, used on a real config
+    let config = Config::parse("port = 8080\ndatabase_url = postgres://db.example.com/app");
+    println!("Port: {}", config.port_or_default());
+    println!("Timeout: {:?}", config.timeout());
+    println!("Database host: {:?}", config.database_host());
+    match config.require("name") {
+        Ok(name) => println!("Name: {name}"),
+        Err(e) => println!("{e}"),
+    }
 }
\ No newline at end of file
@@ -0,0 +1,53 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use pointer_bench::{payload, separate_arc_clones, shared_arc_clones, THREAD_COUNTS};
+use std::{rc::Rc, sync::Arc};
+
+const ELEMENTS: usize = 1000;
+const PER_THREAD: usize = 100_000;
+
+fn clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone");
+    let boxed = Box::new(payload());
+    let rc = Rc::new(payload());
+    let arc = Arc::new(payload());
+    group.bench_function("Box", |b| b.iter(|| black_box(&boxed).clone()));
+    group.bench_function("Rc", |b| b.iter(|| Rc::clone(black_box(&rc))));
+    group.bench_function("Arc", |b| b.iter(|| Arc::clone(black_box(&arc))));
+    group.finish();
+}
+
+fn deref(c: &mut Criterion) {
+    // Sum a value through each kind of pointer. The plain Vec has no
+    // pointers to follow; the rest all have one hop per element.
+    let mut group = c.benchmark_group("deref");
+    group.throughput(Throughput::Elements(ELEMENTS as u64));
+    let values: Vec<u64> = (0..ELEMENTS as u64).collect();
+    let boxes: Vec<Box<u64>> = values.iter().map(|v| Box::new(*v)).collect();
+    let rcs: Vec<Rc<u64>> = values.iter().map(|v| Rc::new(*v)).collect();
+    let arcs: Vec<Arc<u64>> = values.iter().map(|v| Arc::new(*v)).collect();
+    group.bench_function("value", |b| b.iter(|| black_box(&values).iter().sum::<u64>()));
+    group.bench_function("Box", |b| b.iter(|| black_box(&boxes).iter().map(|v| **v).sum::<u64>()));
+    group.bench_function("Rc", |b| b.iter(|| black_box(&rcs).iter().map(|v| **v).sum::<u64>()));
+    group.bench_function("Arc", |b| b.iter(|| black_box(&arcs).iter().map(|v| **v).sum::<u64>()));
+    group.finish();
+}
+
+fn arc_threads(c: &mut Criterion) {
+    // One group per thread count, comparing threads that share an Arc with
+    // threads that have one each
+    for threads in THREAD_COUNTS {
+        let mut group = c.benchmark_group(format!("Arc clone, {threads} threads"));
+        group.throughput(Throughput::Elements((threads * PER_THREAD) as u64));
+        let shared = Arc::new(payload());
+        group.bench_with_input(BenchmarkId::from_parameter("shared"), &threads, |b, &threads| {
+            b.iter(|| shared_arc_clones(&shared, threads, PER_THREAD))
+        });
+        group.bench_with_input(BenchmarkId::from_parameter("separate"), &threads, |b, &threads| {
+            b.iter(|| separate_arc_clones(threads, PER_THREAD))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, clone, deref, arc_threads);
+criterion_main!(benches);
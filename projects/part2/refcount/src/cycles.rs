@@ -0,0 +1,93 @@
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+// Parent and child both hold an Rc to each other. Neither count can reach
+// zero while the other is alive, so neither is ever dropped.
+struct LeakyNode {
+    name: String,
+    parent: RefCell<Option<Rc<LeakyNode>>>,
+    children: RefCell<Vec<Rc<LeakyNode>>>,
+}
+
+impl Drop for LeakyNode {
+    fn drop(&mut self) {
+        println!("Dropping {}", self.name);
+    }
+}
+
+// Children are owned (Rc), but the way back up is only a Weak reference:
+// it doesn't keep the parent alive, so the cycle is broken.
+struct Node {
+    name: String,
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        println!("Dropping {}", self.name);
+    }
+}
+
+fn counts<T>(name: &str, rc: &Rc<T>) {
+    println!(
+        "  {name}: strong = {}, weak = {}",
+        Rc::strong_count(rc),
+        Rc::weak_count(rc)
+    );
+}
+
+pub fn leak() {
+    println!("Rc in both directions:");
+    {
+        let parent = Rc::new(LeakyNode {
+            name: "leaky parent".to_string(),
+            parent: RefCell::new(None),
+            children: RefCell::new(Vec::new()),
+        });
+        let child = Rc::new(LeakyNode {
+            name: "leaky child".to_string(),
+            parent: RefCell::new(Some(parent.clone())),
+            children: RefCell::new(Vec::new()),
+        });
+        parent.children.borrow_mut().push(child.clone());
+        counts("parent", &parent);
+        counts("child", &child);
+        if let Some(parent) = child.parent.borrow().as_ref() {
+            println!("  {}'s parent is {}", child.name, parent.name);
+        }
+        println!("  Leaving the scope...");
+    }
+    // Each node loses one strong reference but still has the other's
+    println!("  ...and nothing was dropped. Both nodes have leaked.");
+}
+
+pub fn no_leak() {
+    println!("Weak for the back-reference:");
+    let child = Rc::new(Node {
+        name: "child".to_string(),
+        parent: RefCell::new(Weak::new()),
+        children: RefCell::new(Vec::new()),
+    });
+    {
+        let parent = Rc::new(Node {
+            name: "parent".to_string(),
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(Vec::new()),
+        });
+        *child.parent.borrow_mut() = Rc::downgrade(&parent);
+        parent.children.borrow_mut().push(child.clone());
+        counts("parent", &parent);
+        counts("child", &child);
+
+        // upgrade() turns the Weak back into an Rc, if it's still alive
+        if let Some(parent) = child.parent.borrow().upgrade() {
+            println!("  {}'s parent is {}", child.name, parent.name);
+        }
+        println!("  Leaving the scope...");
+    }
+    counts("child", &child);
+    if child.parent.borrow().upgrade().is_none() {
+        println!("  {}'s parent is gone", child.name);
+    }
+}
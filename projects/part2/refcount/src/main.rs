@@ -1,3 +1,5 @@
+mod cycles;
+
 use std::rc::Rc;
 
 struct MyStruct {}
@@ -8,11 +10,11 @@ impl Drop for MyStruct {
     }
 }
 
-fn move_it(n: Rc<MyStruct>) {
+fn move_it(_n: Rc<MyStruct>) {
     println!("Moved");
 }
 
-fn ref_it(n: &MyStruct) {
+fn ref_it(_n: &MyStruct) {
     // Do something
 }
 
@@ -20,4 +22,8 @@ fn main() {
     let shared = Rc::new(MyStruct{});
     move_it(shared.clone());
     ref_it(&shared);
-}
\ No newline at end of file
+
+    cycles::leak();
+    println!();
+    cycles::no_leak();
+}
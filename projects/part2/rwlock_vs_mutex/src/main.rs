@@ -0,0 +1,111 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+const READERS: usize = 8;
+const RUN_TIME: Duration = Duration::from_secs(1);
+const DATA_SIZE: u64 = 10_000;
+
+struct Report {
+    reads: u64,
+    writes: u64,
+    worst_write_wait: Duration,
+}
+
+// Readers call `read` as fast as they can; one writer calls `write` and
+// then sleeps for `write_every`, until RUN_TIME is up.
+fn run<R, W>(write_every: Duration, read: R, write: W) -> Report
+where
+    R: Fn() -> u64 + Sync,
+    W: Fn() + Sync,
+{
+    let stop = AtomicBool::new(false);
+    let reads = AtomicU64::new(0);
+
+    let (writes, worst_write_wait) = std::thread::scope(|scope| {
+        for _ in 0..READERS {
+            scope.spawn(|| {
+                while !stop.load(Ordering::Relaxed) {
+                    std::hint::black_box(read());
+                    reads.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+
+        let writer = scope.spawn(|| {
+            let mut writes = 0;
+            let mut worst = Duration::ZERO;
+            while !stop.load(Ordering::Relaxed) {
+                // Most of this is waiting for the readers to let go
+                let start = Instant::now();
+                write();
+                worst = worst.max(start.elapsed());
+                writes += 1;
+                std::thread::sleep(write_every);
+            }
+            (writes, worst)
+        });
+
+        std::thread::sleep(RUN_TIME);
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap()
+    });
+
+    Report {
+        reads: reads.into_inner(),
+        writes,
+        worst_write_wait,
+    }
+}
+
+fn with_mutex(write_every: Duration) -> Report {
+    let data = Mutex::new((0..DATA_SIZE).collect::<Vec<u64>>());
+    run(
+        write_every,
+        || data.lock().unwrap().iter().sum(),
+        || data.lock().unwrap()[0] += 1,
+    )
+}
+
+fn with_rwlock(write_every: Duration) -> Report {
+    let data = RwLock::new((0..DATA_SIZE).collect::<Vec<u64>>());
+    run(
+        write_every,
+        || data.read().unwrap().iter().sum(),
+        || data.write().unwrap()[0] += 1,
+    )
+}
+
+fn main() {
+    let scenarios = [
+        ("read-heavy (write every 10ms)", Duration::from_millis(10)),
+        ("write-heavy (write constantly)", Duration::ZERO),
+    ];
+
+    // Readers can only overlap if there are cores for them to run on
+    let cores = std::thread::available_parallelism().map_or(1, |n| n.get());
+    println!("{READERS} reader threads and 1 writer on {cores} cores, for {RUN_TIME:?} each\n");
+    println!(
+        "| {:<31}| {:<7}| {:>10} | {:>8} | {:>16} |",
+        "scenario", "lock", "reads", "writes", "worst write wait"
+    );
+    println!("|{:-<32}|{:-<8}|{:-<12}|{:-<10}|{:-<18}|", "", "", "", "", "");
+    for (name, write_every) in scenarios {
+        for (lock, report) in [
+            ("Mutex", with_mutex(write_every)),
+            ("RwLock", with_rwlock(write_every)),
+        ] {
+            println!(
+                "| {name:<31}| {lock:<7}| {:>10} | {:>8} | {:>16} |",
+                report.reads,
+                report.writes,
+                format!("{:.1?}", report.worst_write_wait)
+            );
+        }
+    }
+    println!();
+    println!("RwLock lets the readers sum the data at the same time, so it wins when");
+    println!("writes are rare. When they aren't, every write has to wait for all the");
+    println!("readers to leave - watch the worst write wait - and the extra bookkeeping");
+    println!("buys you nothing over a plain Mutex.");
+}
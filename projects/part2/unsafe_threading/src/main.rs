@@ -1,15 +1,19 @@
-fn main() {
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// Two threads add to an unsynchronised static: a data race, and undefined
+// behaviour. Run the tests under Miri to watch it get caught.
+fn racy_count(per_thread: u32) -> u32 {
     static mut COUNTER: u32 = 0;
     std::thread::scope(|scope| {
         let t1 = scope.spawn(|| {
-            for _ in 0 .. 1000000 {
+            for _ in 0 .. per_thread {
                 unsafe {
                     COUNTER += 1;
                 }
             }
         });
         let t2 = scope.spawn(|| {
-            for _ in 0 .. 1000000 {
+            for _ in 0 .. per_thread {
                 unsafe {
                     COUNTER += 1;
                 }
@@ -18,7 +22,55 @@ fn main() {
         let _ = t1.join();
         let _ = t2.join(); // let _ means "ignore" - we're ignoring the result type
     });
-    unsafe {
-        println!("{COUNTER}");
+    unsafe { COUNTER }
+}
+
+// The fix doesn't need unsafe at all: an atomic makes each increment
+// indivisible, and a local means no global state to reset
+fn fixed_count(per_thread: u32) -> u32 {
+    let counter = AtomicU32::new(0);
+    std::thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                for _ in 0 .. per_thread {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    counter.into_inner()
+}
+
+// `cargo run` shows the race, `cargo run -- --fixed` the fix
+fn main() {
+    if std::env::args().any(|arg| arg == "--fixed") {
+        println!("{}", fixed_count(1000000));
+    } else {
+        println!("{}", racy_count(1000000));
+    }
+}
+
+// Miri runs the program in an interpreter that checks every memory access:
+//
+//   rustup +nightly component add miri
+//   cargo +nightly miri test -- --include-ignored
+//
+// `fixed_count_is_exact` passes, and `racy_count_is_a_data_race` stops with
+// "Undefined Behavior: Data race detected".
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_count_is_exact() {
+        assert_eq!(fixed_count(100), 200);
+    }
+
+    // Without Miri the race usually goes unnoticed with counts this small,
+    // which is the point: testing can't prove the racy version wrong
+    #[test]
+    #[ignore = "undefined behaviour - run under Miri to see it detected"]
+    fn racy_count_is_a_data_race() {
+        assert_eq!(racy_count(100), 200);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,105 @@
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+// A job is any closure we can send to another thread and run once
+type Job<T> = Box<dyn FnOnce() -> T + Send + 'static>;
+
+struct WorkerPool<T> {
+    jobs: Option<Sender<Job<T>>>, // Option so Drop can take it and hang up
+    results: Receiver<T>,
+    workers: Vec<JoinHandle<()>>,
+}
+
+impl<T: Send + 'static> WorkerPool<T> {
+    fn new(n: usize) -> Self {
+        let (job_tx, job_rx) = mpsc::channel::<Job<T>>();
+        let (result_tx, result_rx) = mpsc::channel();
+
+        // A Receiver can't be shared between threads on its own, so the
+        // workers take turns with it through a Mutex.
+        let job_rx = Arc::new(Mutex::new(job_rx));
+
+        let workers = (0..n)
+            .map(|id| {
+                let job_rx = job_rx.clone();
+                let result_tx = result_tx.clone();
+                std::thread::spawn(move || loop {
+                    // The lock is released at the end of this statement, so
+                    // other workers can pick up jobs while this one runs.
+                    let job = job_rx.lock().unwrap().recv();
+                    match job {
+                        Ok(job) => {
+                            if result_tx.send(job()).is_err() {
+                                break; // Nobody is listening for results
+                            }
+                        }
+                        Err(_) => {
+                            // Every Sender is gone: no more work is coming
+                            println!("Worker {id} shutting down");
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        Self {
+            jobs: Some(job_tx),
+            results: result_rx,
+            workers,
+        }
+    }
+
+    fn execute<F>(&self, job: F)
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        self.jobs.as_ref().unwrap().send(Box::new(job)).unwrap();
+    }
+
+    fn results(&self) -> &Receiver<T> {
+        &self.results
+    }
+}
+
+impl<T> Drop for WorkerPool<T> {
+    fn drop(&mut self) {
+        // Dropping the sender makes every worker's recv() fail once the
+        // queue is empty, so they finish what's queued and then exit.
+        drop(self.jobs.take());
+        for worker in self.workers.drain(..) {
+            worker.join().unwrap();
+        }
+    }
+}
+
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|i| i * i <= n).all(|i| !n.is_multiple_of(i))
+}
+
+fn main() {
+    let pool = WorkerPool::new(4);
+
+    // Count the primes in ten blocks of numbers
+    let blocks = 10;
+    for block in 0..blocks {
+        pool.execute(move || {
+            let start = block * 100_000;
+            let count = (start..start + 100_000).filter(|n| is_prime(*n)).count();
+            (block, count)
+        });
+    }
+
+    // Results arrive in whatever order the jobs finish
+    let mut total = 0;
+    for _ in 0..blocks {
+        let (block, count) = pool.results().recv().unwrap();
+        println!("Block {block}: {count} primes");
+        total += count;
+    }
+    println!("{total} primes below {}", blocks * 100_000);
+
+    // Hang up and wait for the workers to exit
+    drop(pool);
+}
@@ -0,0 +1,23 @@
+//! Feeds arbitrary bytes to the users file parser. Run it from
+//! projects/part3/auth_core with:
+//!
+//! cargo +nightly fuzz run user_file
+#![no_main]
+
+use auth_core::{users_from_json, users_to_json};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(json) = std::str::from_utf8(data) else {
+        return;
+    };
+    // Anything the parser accepts must survive being saved and loaded again
+    if let Ok(users) = users_from_json(json) {
+        let saved = users_to_json(&users).unwrap();
+        let reloaded = users_to_json(&users_from_json(&saved).unwrap()).unwrap();
+        // Compare as JSON values: HashMap order changes from one map to the next
+        let saved: serde_json::Value = serde_json::from_str(&saved).unwrap();
+        let reloaded: serde_json::Value = serde_json::from_str(&reloaded).unwrap();
+        assert_eq!(saved, reloaded);
+    }
+});
@@ -0,0 +1,94 @@
+use std::{
+    io::Write,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use serde::{Deserialize, Serialize};
+use crate::LoginError;
+
+const AUDIT_PATH: &str = "audit.jsonl";
+
+// AUDIT_LOG names a different file, e.g. to keep tests out of the source tree
+fn audit_path() -> PathBuf {
+    std::env::var_os("AUDIT_LOG").map_or_else(|| PathBuf::from(AUDIT_PATH), PathBuf::from)
+}
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum AuditOutcome {
+    Granted,
+    Denied,
+    Error,
+}
+
+/// One line of the audit log.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Seconds since the UNIX epoch
+    pub timestamp: u64,
+    pub username: String,
+    pub outcome: AuditOutcome,
+    /// Where the attempt came from, e.g. "console" or an IP address
+    pub source: String,
+}
+
+/// Appends a record to the audit log, `audit.jsonl` unless the AUDIT_LOG
+/// environment variable names another file. The file is only ever appended
+/// to, one JSON object per line.
+pub fn record_login_attempt(
+    username: &str,
+    outcome: AuditOutcome,
+    source: &str,
+) -> Result<(), LoginError> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    let record = AuditRecord {
+        timestamp,
+        username: username.to_string(),
+        outcome,
+        source: source.to_string(),
+    };
+    let mut line = serde_json::to_string(&record)?;
+    line.push('\n');
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(audit_path())?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Returns up to `count` of the most recent audit records, oldest first.
+pub fn read_audit_log(count: usize) -> Result<Vec<AuditRecord>, LoginError> {
+    let audit_path = audit_path();
+    if !audit_path.exists() {
+        return Ok(Vec::new());
+    }
+    let log = std::fs::read_to_string(audit_path)?;
+    let mut records = log
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(serde_json::from_str)
+        .collect::<Result<Vec<AuditRecord>, _>>()?;
+    if records.len() > count {
+        records.drain(..records.len() - count);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_audit_round_trip() {
+        crate::test::use_temp_audit_log();
+        record_login_attempt("audit_test_user", AuditOutcome::Denied, "test").unwrap();
+        let records = read_audit_log(usize::MAX).unwrap();
+        assert!(records.iter().any(|r| r.username == "audit_test_user"
+            && r.outcome == AuditOutcome::Denied
+            && r.source == "test"));
+    }
+}
@@ -0,0 +1,102 @@
+use std::path::Path;
+use serde::Deserialize;
+use crate::{hash_password, is_valid_hash, LoginError, LoginRole, User};
+
+const CONFIG_PATH: &str = "login.toml";
+
+/// Settings used to seed the user store the first time it's created.
+///
+/// Values come from the built-in defaults, then `login.toml` (if present),
+/// then the `ADMIN_USER` and `ADMIN_PASSWORD_HASH` environment variables.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub admin_user: String,
+    /// SHA-256 of the admin password, as hex
+    pub admin_password_hash: String,
+    /// Any additional users to create alongside the admin
+    pub users: Vec<User>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            admin_user: "admin".to_string(),
+            admin_password_hash: hash_password("password"),
+            users: vec![User::new("bob", "password", LoginRole::User)],
+        }
+    }
+}
+
+impl Config {
+    /// The defaults, with any environment variable overrides applied.
+    pub fn from_env() -> Result<Self, LoginError> {
+        Self::default().with_env_overrides().validate()
+    }
+
+    /// Reads a TOML config file. Missing fields take their default values.
+    pub fn from_file(path: &Path) -> Result<Self, LoginError> {
+        let config_toml = std::fs::read_to_string(path)?;
+        let config: Config = toml::from_str(&config_toml)?;
+        config.validate()
+    }
+
+    /// Reads `login.toml` if it exists, and then applies environment overrides.
+    pub fn load() -> Result<Self, LoginError> {
+        let config_path = Path::new(CONFIG_PATH);
+        let config = if config_path.exists() {
+            Self::from_file(config_path)?
+        } else {
+            Self::default()
+        };
+        config.with_env_overrides().validate()
+    }
+
+    // Catch a bad hash now, rather than seeding an admin nobody can log in as
+    fn validate(self) -> Result<Self, LoginError> {
+        if !is_valid_hash(&self.admin_password_hash) {
+            return Err(LoginError::InvalidHash(self.admin_user));
+        }
+        Ok(self)
+    }
+
+    fn with_env_overrides(mut self) -> Self {
+        if let Ok(admin_user) = std::env::var("ADMIN_USER") {
+            self.admin_user = admin_user;
+        }
+        if let Ok(hash) = std::env::var("ADMIN_PASSWORD_HASH") {
+            // hash_password produces upper-case hex; tools like sha256sum don't
+            self.admin_password_hash = hash.trim().to_uppercase();
+        }
+        self
+    }
+
+    /// The initial set of users described by this configuration.
+    pub fn seed_users(&self) -> Vec<User> {
+        let admin = User {
+            username: self.admin_user.to_lowercase(),
+            password: self.admin_password_hash.clone(),
+            role: LoginRole::Admin,
+        };
+        std::iter::once(admin).chain(self.users.iter().cloned()).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_partial_config() {
+        let config: Config = toml::from_str("admin_user = \"root\"").unwrap();
+        assert_eq!(config.admin_user, "root");
+        assert_eq!(config.admin_password_hash, hash_password("password"));
+        assert_eq!(config.seed_users().len(), 2);
+    }
+
+    #[test]
+    fn test_invalid_admin_hash() {
+        let config: Config = toml::from_str("admin_password_hash = \"password\"").unwrap();
+        assert!(matches!(config.validate(), Err(LoginError::InvalidHash(user)) if user == "admin"));
+    }
+}
@@ -0,0 +1,126 @@
+use std::{collections::HashMap, time::Duration};
+use ldap3::{dn_escape, LdapConn, LdapConnSettings, LdapError, Scope, SearchEntry};
+use crate::{LoginAction, LoginError, LoginRole, User, UserStore};
+
+// LDAP result code for a failed bind
+const INVALID_CREDENTIALS: u32 = 49;
+
+/// Where the directory is and how users map onto it. See `from_env` for
+/// the variables and their defaults.
+#[derive(Clone, Debug)]
+pub struct LdapConfig {
+    /// e.g. `ldap://localhost:389`
+    pub url: String,
+    /// The DN to bind as, with `{username}` where the username goes
+    pub user_dn_template: String,
+    /// Members of this group (from the user's `memberOf`) are admins;
+    /// everyone else who can bind is a regular user
+    pub admin_group: String,
+    pub timeout: Duration,
+}
+
+impl LdapConfig {
+    /// Reads `LDAP_URL` (required), `LDAP_USER_DN` and `LDAP_ADMIN_GROUP`.
+    pub fn from_env() -> Result<Self, LoginError> {
+        let url = std::env::var("LDAP_URL")
+            .map_err(|_| LoginError::Directory("LDAP_URL is not set".to_string()))?;
+        let user_dn_template = std::env::var("LDAP_USER_DN")
+            .unwrap_or_else(|_| "uid={username},ou=people,dc=example,dc=com".to_string());
+        let admin_group = std::env::var("LDAP_ADMIN_GROUP")
+            .unwrap_or_else(|_| "cn=admins,ou=groups,dc=example,dc=com".to_string());
+        Ok(Self {
+            url,
+            user_dn_template,
+            admin_group,
+            timeout: Duration::from_secs(5),
+        })
+    }
+
+    fn user_dn(&self, username: &str) -> String {
+        // Escape it, or a username like "x,ou=admins" could pick its own DN
+        self.user_dn_template
+            .replace("{username}", &dn_escape(username.to_lowercase()))
+    }
+}
+
+/// Checks credentials by binding to an LDAP directory as the user. The
+/// directory owns the users and their passwords, so they can't be listed
+/// or saved from here - manage them with your directory's own tools.
+pub struct LdapStore {
+    config: LdapConfig,
+}
+
+impl LdapStore {
+    pub fn new(config: LdapConfig) -> Self {
+        Self { config }
+    }
+
+    pub fn from_env() -> Result<Self, LoginError> {
+        Ok(Self::new(LdapConfig::from_env()?))
+    }
+
+    fn connect(&self) -> Result<LdapConn, LoginError> {
+        let settings = LdapConnSettings::new().set_conn_timeout(self.config.timeout);
+        LdapConn::with_settings(settings, &self.config.url).map_err(directory_error)
+    }
+
+    fn role(&self, ldap: &mut LdapConn, dn: &str) -> Result<LoginRole, LoginError> {
+        let (entries, _) = ldap
+            .search(dn, Scope::Base, "(objectClass=*)", vec!["memberOf"])
+            .map_err(directory_error)?
+            .success()
+            .map_err(directory_error)?;
+        let is_admin = entries.into_iter().any(|entry| {
+            SearchEntry::construct(entry)
+                .attrs
+                .get("memberOf")
+                .is_some_and(|groups| {
+                    groups
+                        .iter()
+                        .any(|group| group.eq_ignore_ascii_case(&self.config.admin_group))
+                })
+        });
+        Ok(if is_admin {
+            LoginRole::Admin
+        } else {
+            LoginRole::User
+        })
+    }
+}
+
+fn directory_error(e: LdapError) -> LoginError {
+    LoginError::Directory(e.to_string())
+}
+
+impl UserStore for LdapStore {
+    fn get_users(&self) -> Result<HashMap<String, User>, LoginError> {
+        Err(LoginError::Directory(
+            "users in LDAP can't be listed through the login library".to_string(),
+        ))
+    }
+
+    fn save_users(&self, _users: &HashMap<String, User>) -> Result<(), LoginError> {
+        Err(LoginError::Directory(
+            "users in LDAP can't be changed through the login library".to_string(),
+        ))
+    }
+
+    fn check_credentials(&self, username: &str, password: &str) -> Result<LoginAction, LoginError> {
+        // A bind with an empty password is an "unauthenticated bind", which
+        // many servers accept for any DN. Never treat that as a login.
+        if password.is_empty() {
+            return Ok(LoginAction::Denied);
+        }
+
+        let dn = self.config.user_dn(username);
+        let mut ldap = self.connect()?;
+        let bind = ldap.simple_bind(&dn, password).map_err(directory_error)?;
+        let action = match bind.rc {
+            0 => LoginAction::Granted(self.role(&mut ldap, &dn)?),
+            INVALID_CREDENTIALS => LoginAction::Denied,
+            _ => return Err(directory_error(LdapError::from(bind))),
+        };
+        let _ = ldap.unbind();
+        Ok(action)
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+use serde::{Serialize, Deserialize};
+use thiserror::Error;
+
+mod read_line;
+pub use read_line::{prompt, prompt_with_validator, read_line, read_password};
+
+mod store;
+pub use store::{get_users, save_users, users_from_json, users_to_json, JsonFileStore, UserStore};
+
+#[cfg(feature = "audit")]
+mod audit;
+#[cfg(feature = "audit")]
+pub use audit::{read_audit_log, record_login_attempt, AuditOutcome, AuditRecord};
+
+mod config;
+pub use config::Config;
+
+mod messages;
+pub use messages::Messages;
+
+#[cfg(feature = "ldap")]
+mod ldap_store;
+#[cfg(feature = "ldap")]
+pub use ldap_store::{LdapConfig, LdapStore};
+
+mod rate_limit;
+pub use rate_limit::{Limit, RateLimited, RateLimiter};
+
+mod permissions;
+pub use permissions::{get_roles, save_roles, Permission, Permissions};
+
+mod registration;
+pub use registration::{register, register_with_store, RegistrationError};
+
+#[cfg(feature = "policy")]
+mod password_policy;
+#[cfg(feature = "policy")]
+pub use password_policy::{CharacterClass, PasswordPolicy, PolicyViolation};
+
+#[derive(Debug, Error)]
+pub enum LoginError {
+    #[error("Unable to access the users file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("The users file is corrupted: {0}")]
+    CorruptUserFile(#[from] serde_json::Error),
+    #[error("The stored password hash for {0} is invalid")]
+    InvalidHash(String),
+    #[error("The login config file is invalid: {0}")]
+    Config(#[from] toml::de::Error),
+    #[error("The directory server failed: {0}")]
+    Directory(String),
+}
+
+#[derive(PartialEq, Debug, Serialize, Deserialize)]
+pub enum LoginAction {
+    Granted(LoginRole),
+    /// A wrong password, or a username that doesn't exist. They get the
+    /// same answer, so logging in can't be used to find out who has an
+    /// account.
+    Denied,
+}
+
+/// What happened when someone tried to change their password.
+#[derive(PartialEq, Debug)]
+pub enum PasswordChange {
+    Changed,
+    /// The old password was wrong, or the user doesn't exist
+    Denied,
+    /// The new password breaks the password policy
+    #[cfg(feature = "policy")]
+    Rejected(Vec<PolicyViolation>),
+}
+
+#[derive(PartialEq, Debug, Clone, Serialize, Deserialize)]
+pub enum LoginRole {
+    Admin,
+    User,
+    Custom {
+        name: String,
+        permissions: Permissions,
+    },
+}
+
+impl LoginRole {
+    pub fn permissions(&self) -> Permissions {
+        match self {
+            LoginRole::Admin => Permissions::all(),
+            LoginRole::User => Permissions::default().with(Permission::ReadReports),
+            LoginRole::Custom { permissions, .. } => *permissions,
+        }
+    }
+
+    pub fn has_permission(&self, permission: Permission) -> bool {
+        self.permissions().contains(permission)
+    }
+}
+
+impl fmt::Display for LoginRole {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoginRole::Admin => write!(f, "Admin"),
+            LoginRole::User => write!(f, "User"),
+            LoginRole::Custom { name, .. } => write!(f, "{name}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct User {
+    pub username: String,
+    pub password: String,
+    pub role: LoginRole,
+}
+
+impl User {
+    pub fn new(username: &str, password: &str, role: LoginRole) -> User {
+        User {
+            username: username.to_lowercase(),
+            password: hash_password(password),
+            role,
+        }
+    }
+}
+
+pub fn hash_password(password: &str) -> String {
+    use sha2::Digest;
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(password);
+    format!("{:X}", hasher.finalize())
+}
+
+pub(crate) fn is_valid_hash(hash: &str) -> bool {
+    // A SHA-256 digest is 32 bytes, formatted as 64 hex digits
+    hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+pub fn login(username: &str, password: &str) -> Result<LoginAction, LoginError> {
+    login_with_source(username, password, "console")
+}
+
+/// Checks the credentials against the default `users.json` store.
+pub fn login_with_source(
+    username: &str,
+    password: &str,
+    source: &str,
+) -> Result<LoginAction, LoginError> {
+    login_with_store(&JsonFileStore::default(), username, password, source)
+}
+
+/// Checks the credentials against `store`. With the `audit` feature, the
+/// attempt and where it came from are recorded in the audit log; if that
+/// fails, a warning is logged and the login goes ahead.
+pub fn login_with_store(
+    store: &dyn UserStore,
+    username: &str,
+    password: &str,
+    source: &str,
+) -> Result<LoginAction, LoginError> {
+    let result = store.check_credentials(username, password);
+    match &result {
+        Ok(LoginAction::Granted(role)) => tracing::info!(username, source, %role, "Login granted"),
+        Ok(LoginAction::Denied) => tracing::info!(username, source, "Login denied"),
+        Err(e) => tracing::error!(username, source, "Unable to check credentials: {e}"),
+    }
+
+    #[cfg(feature = "audit")]
+    {
+        let outcome = match &result {
+            Ok(LoginAction::Granted(_)) => AuditOutcome::Granted,
+            Ok(LoginAction::Denied) => AuditOutcome::Denied,
+            Err(_) => AuditOutcome::Error,
+        };
+        // Losing an audit record shouldn't lock everyone out
+        if let Err(e) = record_login_attempt(username, outcome, source) {
+            tracing::warn!(username, source, "Unable to record the login attempt: {e}");
+        }
+    }
+    #[cfg(not(feature = "audit"))]
+    let _ = source;
+
+    result
+}
+
+/// Changes a password in the default `users.json` store.
+pub fn change_password(
+    username: &str,
+    old_password: &str,
+    new_password: &str,
+) -> Result<PasswordChange, LoginError> {
+    change_password_with_store(&JsonFileStore::default(), username, old_password, new_password)
+}
+
+/// Checks `old_password`, then replaces it with `new_password` in `store`.
+/// With the `policy` feature, the new password must pass the default
+/// `PasswordPolicy`.
+pub fn change_password_with_store(
+    store: &dyn UserStore,
+    username: &str,
+    old_password: &str,
+    new_password: &str,
+) -> Result<PasswordChange, LoginError> {
+    if store.check_credentials(username, old_password)? == LoginAction::Denied {
+        tracing::info!(username, "Password change denied");
+        return Ok(PasswordChange::Denied);
+    }
+
+    #[cfg(feature = "policy")]
+    if let Err(violations) = PasswordPolicy::default().validate(new_password) {
+        tracing::info!(username, rules_broken = violations.len(), "New password rejected");
+        return Ok(PasswordChange::Rejected(violations));
+    }
+
+    let mut users = store.get_users()?;
+    let Some(user) = users.get_mut(&username.to_lowercase()) else {
+        return Ok(PasswordChange::Denied);
+    };
+    user.password = hash_password(new_password);
+    store.save_users(&users)?;
+    tracing::info!(username, "Password changed");
+    Ok(PasswordChange::Changed)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Logins made by the tests are audited in the temp directory. The
+    // variable is set once, before any test that logs in reads it.
+    pub(crate) fn use_temp_audit_log() {
+        static ONCE: std::sync::Once = std::sync::Once::new();
+        ONCE.call_once(|| {
+            let name = format!("auth_core_audit_{}.jsonl", std::process::id());
+            std::env::set_var("AUDIT_LOG", std::env::temp_dir().join(name));
+        });
+    }
+
+    #[test]
+    fn test_enums() {
+        use_temp_audit_log();
+        // Doesn't exist yet, so it's seeded with the default users
+        let path = std::env::temp_dir().join(format!("auth_core_enums_{}.json", std::process::id()));
+        let store = JsonFileStore::new(&path);
+        let login = |username, password| login_with_store(&store, username, password, "test").unwrap();
+        assert_eq!(login("admin", "password"), LoginAction::Granted(LoginRole::Admin));
+        assert_eq!(login("bob", "password"), LoginAction::Granted(LoginRole::User));
+        assert_eq!(login("admin", "wrong"), LoginAction::Denied);
+        assert_eq!(login("wrong", "password"), LoginAction::Denied);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_change_password() {
+        let path = std::env::temp_dir().join("auth_core_change_password.json");
+        let store = JsonFileStore::new(&path);
+        let users = [("carol".to_string(), User::new("carol", "Old1Secret", LoginRole::User))];
+        store.save_users(&users.into_iter().collect()).unwrap();
+
+        let change = |old, new| change_password_with_store(&store, "carol", old, new).unwrap();
+        assert_eq!(change("wrong", "New1Secret"), PasswordChange::Denied);
+        #[cfg(feature = "policy")]
+        assert!(matches!(change("Old1Secret", "short"), PasswordChange::Rejected(_)));
+        assert_eq!(change("Old1Secret", "New1Secret"), PasswordChange::Changed);
+
+        let check = |password| store.check_credentials("carol", password).unwrap();
+        assert_eq!(check("Old1Secret"), LoginAction::Denied);
+        assert_eq!(check("New1Secret"), LoginAction::Granted(LoginRole::User));
+        std::fs::remove_file(path).unwrap();
+    }
+}
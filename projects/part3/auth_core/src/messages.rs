@@ -0,0 +1,138 @@
+#[cfg(feature = "policy")]
+use crate::{CharacterClass, PolicyViolation};
+use std::{collections::HashMap, fmt::Display};
+
+// Every locale the login tools ship with. To add a translation, copy
+// locales/en.toml, translate the right-hand sides and list it here.
+const CATALOGS: &[(&str, &str)] = &[
+    ("en", include_str!("../locales/en.toml")),
+    ("fr", include_str!("../locales/fr.toml")),
+];
+
+const FALLBACK_LOCALE: &str = "en";
+
+type Catalog = HashMap<String, String>;
+
+fn parse_catalog(locale: &str) -> Option<Catalog> {
+    let (_, source) = CATALOGS.iter().find(|(name, _)| *name == locale)?;
+    Some(toml::from_str(source).unwrap_or_else(|e| panic!("locales/{locale}.toml is invalid: {e}")))
+}
+
+/// Turns a POSIX locale such as `fr_FR.UTF-8` into a language code (`fr`).
+fn language(locale: &str) -> &str {
+    locale
+        .split(['_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+}
+
+/// User-facing text, looked up by key in a per-language TOML catalog.
+/// Placeholders such as `{username}` are filled in from the arguments.
+pub struct Messages {
+    locale: String,
+    catalog: Catalog,
+    fallback: Catalog,
+}
+
+impl Messages {
+    /// Picks the locale the same way `gettext` does: `LC_ALL`, then
+    /// `LC_MESSAGES`, then `LANG`. Unknown locales fall back to English.
+    pub fn from_env() -> Self {
+        let locale = ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        Self::for_locale(&locale)
+    }
+
+    pub fn for_locale(locale: &str) -> Self {
+        let fallback = parse_catalog(FALLBACK_LOCALE).unwrap();
+        let language = language(locale);
+        match parse_catalog(language) {
+            Some(catalog) => Self {
+                locale: language.to_string(),
+                catalog,
+                fallback,
+            },
+            None => Self {
+                locale: FALLBACK_LOCALE.to_string(),
+                catalog: fallback.clone(),
+                fallback,
+            },
+        }
+    }
+
+    /// The language code of the catalog in use, e.g. `en`.
+    pub fn locale(&self) -> &str {
+        &self.locale
+    }
+
+    /// Looks up `key` and replaces each `{name}` with its argument. Keys
+    /// missing from the translation use the English text; keys missing
+    /// altogether come back unchanged so they are easy to spot.
+    pub fn get(&self, key: &str, args: &[(&str, &dyn Display)]) -> String {
+        let Some(template) = self.catalog.get(key).or_else(|| self.fallback.get(key)) else {
+            return key.to_string();
+        };
+        args.iter().fold(template.clone(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), &value.to_string())
+        })
+    }
+
+    /// Explains one broken password rule, as a bullet point.
+    #[cfg(feature = "policy")]
+    pub fn violation(&self, violation: &PolicyViolation) -> String {
+        match violation {
+            PolicyViolation::TooShort { min_length, actual } => self.get(
+                "policy-too-short",
+                &[("min_length", min_length), ("actual", actual)],
+            ),
+            PolicyViolation::MissingCharacterClass(class) => {
+                let class = self.get(
+                    match class {
+                        CharacterClass::Lowercase => "class-lowercase",
+                        CharacterClass::Uppercase => "class-uppercase",
+                        CharacterClass::Digit => "class-digit",
+                        CharacterClass::Symbol => "class-symbol",
+                    },
+                    &[],
+                );
+                self.get("policy-missing-class", &[("class", &class)])
+            }
+            PolicyViolation::CommonPassword => self.get("policy-common", &[]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_messages() {
+        let en = Messages::for_locale("en_GB.UTF-8");
+        let fr = Messages::for_locale("fr_FR.UTF-8");
+        assert_eq!(en.locale(), "en");
+        assert_eq!(fr.locale(), "fr");
+        assert_eq!(Messages::for_locale("xx_XX").locale(), "en");
+        assert_eq!(Messages::for_locale("").locale(), "en");
+
+        let username = "bob".to_string();
+        assert_eq!(en.get("login-admin", &[("username", &username)]), "Welcome bob, you are an admin.");
+        assert!(fr.get("login-admin", &[("username", &username)]).contains("bob"));
+        assert_eq!(en.get("no-such-key", &[]), "no-such-key");
+    }
+
+    #[test]
+    fn test_catalogs_have_the_same_keys() {
+        let english = parse_catalog(FALLBACK_LOCALE).unwrap();
+        for (locale, _) in CATALOGS {
+            let catalog = parse_catalog(locale).unwrap();
+            let mut missing: Vec<_> = english.keys().filter(|key| !catalog.contains_key(*key)).collect();
+            missing.sort();
+            assert!(missing.is_empty(), "{locale} is missing {missing:?}");
+            assert_eq!(catalog.len(), english.len(), "{locale} has extra keys");
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use std::fmt;
+
+/// Classes of character that a policy can insist appear in a password.
+#[derive(PartialEq, Debug, Clone, Copy)]
+pub enum CharacterClass {
+    Lowercase,
+    Uppercase,
+    Digit,
+    Symbol,
+}
+
+impl CharacterClass {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            CharacterClass::Lowercase => c.is_lowercase(),
+            CharacterClass::Uppercase => c.is_uppercase(),
+            CharacterClass::Digit => c.is_ascii_digit(),
+            CharacterClass::Symbol => !c.is_alphanumeric() && !c.is_whitespace(),
+        }
+    }
+}
+
+/// A single reason that a password was rejected.
+#[derive(PartialEq, Debug, Clone)]
+pub enum PolicyViolation {
+    TooShort { min_length: usize, actual: usize },
+    MissingCharacterClass(CharacterClass),
+    CommonPassword,
+}
+
+impl fmt::Display for PolicyViolation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PolicyViolation::TooShort { min_length, actual } => {
+                write!(f, "must be at least {min_length} characters long (got {actual})")
+            }
+            PolicyViolation::MissingCharacterClass(class) => {
+                let name = match class {
+                    CharacterClass::Lowercase => "a lowercase letter",
+                    CharacterClass::Uppercase => "an uppercase letter",
+                    CharacterClass::Digit => "a digit",
+                    CharacterClass::Symbol => "a symbol",
+                };
+                write!(f, "must contain {name}")
+            }
+            PolicyViolation::CommonPassword => write!(f, "is too common"),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PasswordPolicy {
+    pub min_length: usize,
+    pub required_classes: Vec<CharacterClass>,
+    pub deny_list: Vec<String>,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 8,
+            required_classes: vec![
+                CharacterClass::Lowercase,
+                CharacterClass::Uppercase,
+                CharacterClass::Digit,
+            ],
+            deny_list: [
+                "password", "password1", "12345678", "123456789", "qwerty123",
+                "letmein", "welcome1", "iloveyou", "admin123", "changeme",
+            ]
+            .iter()
+            .map(|s| s.to_string())
+            .collect(),
+        }
+    }
+}
+
+impl PasswordPolicy {
+    /// Check a password against the policy, returning every rule it breaks.
+    pub fn validate(&self, password: &str) -> Result<(), Vec<PolicyViolation>> {
+        let mut violations = Vec::new();
+
+        let length = password.chars().count();
+        if length < self.min_length {
+            violations.push(PolicyViolation::TooShort {
+                min_length: self.min_length,
+                actual: length,
+            });
+        }
+
+        for class in self.required_classes.iter() {
+            if !password.chars().any(|c| class.matches(c)) {
+                violations.push(PolicyViolation::MissingCharacterClass(*class));
+            }
+        }
+
+        let lowercase = password.to_lowercase();
+        if self.deny_list.iter().any(|denied| denied.to_lowercase() == lowercase) {
+            violations.push(PolicyViolation::CommonPassword);
+        }
+
+        if violations.is_empty() {
+            Ok(())
+        } else {
+            Err(violations)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_policy() {
+        let policy = PasswordPolicy::default();
+        assert_eq!(policy.validate("Sup3rSecret"), Ok(()));
+        assert_eq!(
+            policy.validate("Ab1"),
+            Err(vec![PolicyViolation::TooShort { min_length: 8, actual: 3 }])
+        );
+        assert_eq!(
+            policy.validate("PASSWORD1"),
+            Err(vec![
+                PolicyViolation::MissingCharacterClass(CharacterClass::Lowercase),
+                PolicyViolation::CommonPassword,
+            ])
+        );
+    }
+}
@@ -0,0 +1,142 @@
+use std::{collections::HashMap, fmt, path::Path, str::FromStr};
+use serde::{Deserialize, Serialize};
+use crate::LoginError;
+
+#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum Permission {
+    ReadReports,
+    ManageUsers,
+    Shutdown,
+}
+
+impl Permission {
+    pub const ALL: [Permission; 3] = [
+        Permission::ReadReports,
+        Permission::ManageUsers,
+        Permission::Shutdown,
+    ];
+
+    fn bit(&self) -> u8 {
+        match self {
+            Permission::ReadReports => 1,
+            Permission::ManageUsers => 1 << 1,
+            Permission::Shutdown => 1 << 2,
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace(['-', '_'], "").as_str() {
+            "readreports" => Ok(Permission::ReadReports),
+            "manageusers" => Ok(Permission::ManageUsers),
+            "shutdown" => Ok(Permission::Shutdown),
+            _ => Err(format!("Unknown permission: {s}")),
+        }
+    }
+}
+
+/// A set of permissions, stored as a bitfield. In JSON it's written as
+/// a list of permission names so the files stay readable.
+#[derive(PartialEq, Eq, Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(from = "Vec<Permission>", into = "Vec<Permission>")]
+pub struct Permissions(u8);
+
+impl Permissions {
+    pub fn all() -> Self {
+        Permission::ALL.into_iter().collect()
+    }
+
+    pub fn with(self, permission: Permission) -> Self {
+        Self(self.0 | permission.bit())
+    }
+
+    pub fn contains(&self, permission: Permission) -> bool {
+        self.0 & permission.bit() != 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Permission> + '_ {
+        Permission::ALL.into_iter().filter(|p| self.contains(*p))
+    }
+}
+
+impl FromIterator<Permission> for Permissions {
+    fn from_iter<I: IntoIterator<Item = Permission>>(iter: I) -> Self {
+        iter.into_iter().fold(Permissions::default(), |set, p| set.with(p))
+    }
+}
+
+impl From<Vec<Permission>> for Permissions {
+    fn from(permissions: Vec<Permission>) -> Self {
+        permissions.into_iter().collect()
+    }
+}
+
+impl From<Permissions> for Vec<Permission> {
+    fn from(permissions: Permissions) -> Self {
+        permissions.iter().collect()
+    }
+}
+
+impl fmt::Display for Permissions {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let names: Vec<String> = self.iter().map(|p| format!("{p:?}")).collect();
+        write!(f, "{}", names.join(", "))
+    }
+}
+
+fn get_default_roles() -> HashMap<String, Permissions> {
+    let mut roles = HashMap::new();
+    roles.insert(
+        "auditor".to_string(),
+        Permissions::default().with(Permission::ReadReports),
+    );
+    roles
+}
+
+/// Loads the custom role definitions, creating the file with a default
+/// set if it doesn't exist yet.
+pub fn get_roles() -> Result<HashMap<String, Permissions>, LoginError> {
+    let roles_path = Path::new("roles.json");
+    if roles_path.exists() {
+        let roles_json = std::fs::read_to_string(roles_path)?;
+        let roles: HashMap<String, Permissions> = serde_json::from_str(&roles_json)?;
+        Ok(roles)
+    } else {
+        let roles = get_default_roles();
+        save_roles(&roles)?;
+        Ok(roles)
+    }
+}
+
+pub fn save_roles(roles: &HashMap<String, Permissions>) -> Result<(), LoginError> {
+    let roles_path = Path::new("roles.json");
+    let roles_json = serde_json::to_string(&roles)?;
+    std::fs::write(roles_path, roles_json)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LoginRole;
+
+    #[test]
+    fn test_permissions() {
+        assert!(LoginRole::Admin.has_permission(Permission::Shutdown));
+        assert!(LoginRole::User.has_permission(Permission::ReadReports));
+        assert!(!LoginRole::User.has_permission(Permission::ManageUsers));
+
+        let custom = LoginRole::Custom {
+            name: "operator".to_string(),
+            permissions: Permissions::default().with(Permission::Shutdown),
+        };
+        assert!(custom.has_permission(Permission::Shutdown));
+        assert!(!custom.has_permission(Permission::ReadReports));
+
+        let json = serde_json::to_string(&custom).unwrap();
+        assert_eq!(serde_json::from_str::<LoginRole>(&json).unwrap(), custom);
+    }
+}
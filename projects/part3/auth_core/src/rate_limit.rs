@@ -0,0 +1,223 @@
+use std::{
+    collections::HashMap,
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// How many attempts are allowed: `burst` straight away, then one more every
+/// `refill_every`.
+#[derive(Clone, Copy, Debug)]
+pub struct Limit {
+    pub burst: u32,
+    pub refill_every: Duration,
+}
+
+impl Limit {
+    pub const fn new(burst: u32, refill_every: Duration) -> Self {
+        Self { burst, refill_every }
+    }
+}
+
+/// A bucket of `burst` tokens. Each attempt takes one, and they trickle back
+/// at one per `refill_every`.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn full(limit: &Limit, now: Instant) -> Self {
+        Self {
+            tokens: limit.burst as f64,
+            last_refill: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &Limit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        let earned = elapsed.as_secs_f64() / limit.refill_every.as_secs_f64();
+        self.tokens = (self.tokens + earned).min(limit.burst as f64);
+        self.last_refill = now;
+    }
+
+    /// How long until there's a whole token, or zero if there is one now
+    fn wait_time(&self, limit: &Limit) -> Duration {
+        if self.tokens >= 1.0 {
+            Duration::ZERO
+        } else {
+            limit.refill_every.mul_f64(1.0 - self.tokens)
+        }
+    }
+}
+
+/// Returned when an attempt is over the limit.
+#[derive(Debug, PartialEq)]
+pub struct RateLimited {
+    pub retry_after: Duration,
+}
+
+impl RateLimited {
+    /// Whole seconds, rounded up, for a `Retry-After` header
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after.as_secs() + u64::from(self.retry_after.subsec_nanos() > 0)
+    }
+}
+
+impl fmt::Display for RateLimited {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Too many login attempts. Try again in {} seconds.",
+            self.retry_after_secs()
+        )
+    }
+}
+
+/// Throttles login attempts per username and per source (an IP address,
+/// or "console"). Guessing one user's password hits the first limit;
+/// spraying guesses across many users from one place hits the second.
+pub struct RateLimiter {
+    user_limit: Limit,
+    source_limit: Limit,
+    users: HashMap<String, TokenBucket>,
+    sources: HashMap<String, TokenBucket>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self::new(
+            Limit::new(3, Duration::from_secs(20)),
+            Limit::new(10, Duration::from_secs(6)),
+        )
+    }
+}
+
+impl RateLimiter {
+    pub fn new(user_limit: Limit, source_limit: Limit) -> Self {
+        Self {
+            user_limit,
+            source_limit,
+            users: HashMap::new(),
+            sources: HashMap::new(),
+        }
+    }
+
+    /// Call before every login attempt. Takes a token from both buckets,
+    /// or from neither if either is empty.
+    pub fn check(&mut self, username: &str, source: &str) -> Result<(), RateLimited> {
+        self.check_at(username, source, Instant::now())
+    }
+
+    pub fn check_at(&mut self, username: &str, source: &str, now: Instant) -> Result<(), RateLimited> {
+        // "Bob" and "BOB" are the same account, so they share a bucket
+        let user = self
+            .users
+            .entry(username.to_lowercase())
+            .or_insert_with(|| TokenBucket::full(&self.user_limit, now));
+        user.refill(&self.user_limit, now);
+        let source_bucket = self
+            .sources
+            .entry(source.to_string())
+            .or_insert_with(|| TokenBucket::full(&self.source_limit, now));
+        source_bucket.refill(&self.source_limit, now);
+
+        let retry_after = user
+            .wait_time(&self.user_limit)
+            .max(source_bucket.wait_time(&self.source_limit));
+        if retry_after > Duration::ZERO {
+            return Err(RateLimited { retry_after });
+        }
+        user.tokens -= 1.0;
+        source_bucket.tokens -= 1.0;
+        Ok(())
+    }
+
+    /// Forgets buckets that have refilled completely - they'd be recreated
+    /// full anyway - so the maps don't grow forever.
+    pub fn prune(&mut self) {
+        let now = Instant::now();
+        let (user_limit, source_limit) = (self.user_limit, self.source_limit);
+        self.users.retain(|_, bucket| {
+            bucket.refill(&user_limit, now);
+            bucket.tokens < user_limit.burst as f64
+        });
+        self.sources.retain(|_, bucket| {
+            bucket.refill(&source_limit, now);
+            bucket.tokens < source_limit.burst as f64
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn limiter() -> RateLimiter {
+        RateLimiter::new(
+            Limit::new(2, Duration::from_secs(10)),
+            Limit::new(3, Duration::from_secs(10)),
+        )
+    }
+
+    #[test]
+    fn allows_a_burst_then_throttles() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        assert!(limiter.check_at("bob", "1.2.3.4", now).is_ok());
+        assert!(limiter.check_at("bob", "1.2.3.4", now).is_ok());
+        assert_eq!(
+            limiter.check_at("bob", "1.2.3.4", now),
+            Err(RateLimited {
+                retry_after: Duration::from_secs(10)
+            })
+        );
+    }
+
+    #[test]
+    fn tokens_refill_over_time() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        limiter.check_at("bob", "console", now).unwrap();
+        limiter.check_at("bob", "console", now).unwrap();
+
+        let later = now + Duration::from_secs(4);
+        let err = limiter.check_at("bob", "console", later).unwrap_err();
+        assert_eq!(err.retry_after_secs(), 6);
+
+        let later = now + Duration::from_secs(10);
+        assert!(limiter.check_at("bob", "console", later).is_ok());
+    }
+
+    #[test]
+    fn source_limit_covers_many_users() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        for user in ["alice", "bob", "carol"] {
+            assert!(limiter.check_at(user, "1.2.3.4", now).is_ok());
+        }
+        assert!(limiter.check_at("dave", "1.2.3.4", now).is_err());
+        // A different source isn't affected
+        assert!(limiter.check_at("dave", "5.6.7.8", now).is_ok());
+    }
+
+    #[test]
+    fn username_case_shares_a_bucket() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        limiter.check_at("bob", "a", now).unwrap();
+        limiter.check_at("Bob", "b", now).unwrap();
+        assert!(limiter.check_at("BOB", "c", now).is_err());
+    }
+
+    #[test]
+    fn refused_attempts_dont_use_up_the_other_bucket() {
+        let mut limiter = limiter();
+        let now = Instant::now();
+        limiter.check_at("bob", "a", now).unwrap();
+        limiter.check_at("bob", "a", now).unwrap();
+        // bob's bucket is empty, so source "a" keeps its last token
+        assert!(limiter.check_at("bob", "a", now).is_err());
+        assert!(limiter.check_at("alice", "a", now).is_ok());
+    }
+}
@@ -0,0 +1,52 @@
+use crate::Messages;
+use std::{io, str::FromStr};
+
+pub fn read_line() -> String {
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .expect("Failed to read line");
+    input.trim().to_string()
+}
+
+/// Reads a line from the terminal with echo turned off, so the
+/// password isn't displayed as it is typed.
+pub fn read_password() -> String {
+    let input = rpassword::read_password().expect("Failed to read password");
+    input.trim().to_string()
+}
+
+// Unlike read_line, tells the end of input apart from an empty line
+fn read_line_or_eof() -> io::Result<String> {
+    let mut input = String::new();
+    if std::io::stdin().read_line(&mut input)? == 0 {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "no more input"));
+    }
+    Ok(input.trim().to_string())
+}
+
+/// Prints `msg` and keeps asking until the input parses as a `T`.
+/// Fails with `UnexpectedEof` if stdin runs out first.
+pub fn prompt<T: FromStr>(msg: &str) -> io::Result<T> {
+    prompt_with_validator(msg, |_| Ok(()))
+}
+
+/// Like `prompt`, but the parsed value must also pass `validator`.
+/// The validator returns the reason to show the user on failure.
+pub fn prompt_with_validator<T, F>(msg: &str, validator: F) -> io::Result<T>
+where
+    T: FromStr,
+    F: Fn(&T) -> Result<(), String>,
+{
+    loop {
+        println!("{msg}");
+        let input = read_line_or_eof()?;
+        match input.parse::<T>() {
+            Ok(value) => match validator(&value) {
+                Ok(()) => return Ok(value),
+                Err(reason) => println!("{reason}"),
+            },
+            Err(_) => println!("{}", Messages::from_env().get("invalid-input", &[("input", &input)])),
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use crate::{JsonFileStore, LoginError, LoginRole, User, UserStore};
+#[cfg(feature = "policy")]
+use crate::{PasswordPolicy, PolicyViolation};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RegistrationError {
+    #[error("The username can't be empty")]
+    EmptyUsername,
+    #[error("The username {0} is already taken")]
+    UsernameTaken(String),
+    #[cfg(feature = "policy")]
+    #[error("The password is too weak")]
+    WeakPassword(Vec<PolicyViolation>),
+    #[error(transparent)]
+    Login(#[from] LoginError),
+}
+
+/// Creates a regular user in the default `users.json` store.
+pub fn register(username: &str, password: &str) -> Result<User, RegistrationError> {
+    register_with_store(&JsonFileStore::default(), username, password)
+}
+
+/// Adds `username` to `store` with the `User` role. Usernames are compared
+/// ignoring case, so "Bob" can't register if "bob" exists. With the `policy`
+/// feature, the password must pass the default `PasswordPolicy`.
+pub fn register_with_store(
+    store: &dyn UserStore,
+    username: &str,
+    password: &str,
+) -> Result<User, RegistrationError> {
+    let username = username.trim();
+    if username.is_empty() {
+        return Err(RegistrationError::EmptyUsername);
+    }
+
+    #[cfg(feature = "policy")]
+    PasswordPolicy::default()
+        .validate(password)
+        .map_err(RegistrationError::WeakPassword)?;
+
+    let mut users = store.get_users()?;
+    // The file may have been edited by hand, so check the names as well as the keys
+    let lowercase = username.to_lowercase();
+    let taken = users
+        .iter()
+        .any(|(key, user)| key.to_lowercase() == lowercase || user.username.to_lowercase() == lowercase);
+    if taken {
+        return Err(RegistrationError::UsernameTaken(username.to_string()));
+    }
+
+    let user = User::new(username, password, LoginRole::User);
+    users.insert(user.username.clone(), user.clone());
+    store.save_users(&users)?;
+    tracing::info!(username = user.username, "User registered");
+    Ok(user)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::LoginAction;
+
+    #[test]
+    fn test_register() {
+        let path = std::env::temp_dir().join("auth_core_register.json");
+        let store = JsonFileStore::new(&path);
+        let users = [("bob".to_string(), User::new("bob", "Bob1Secret", LoginRole::User))];
+        store.save_users(&users.into_iter().collect()).unwrap();
+
+        let user = register_with_store(&store, "Dave", "Dave1Secret").unwrap();
+        assert_eq!(user.username, "dave");
+        assert_eq!(user.role, LoginRole::User);
+        assert_eq!(
+            store.check_credentials("dave", "Dave1Secret").unwrap(),
+            LoginAction::Granted(LoginRole::User)
+        );
+
+        assert!(matches!(
+            register_with_store(&store, "BOB", "Bob2Secret"),
+            Err(RegistrationError::UsernameTaken(_))
+        ));
+        assert!(matches!(
+            register_with_store(&store, "  ", "Eve1Secret"),
+            Err(RegistrationError::EmptyUsername)
+        ));
+        #[cfg(feature = "policy")]
+        assert!(matches!(
+            register_with_store(&store, "eve", "short"),
+            Err(RegistrationError::WeakPassword(_))
+        ));
+        std::fs::remove_file(path).unwrap();
+    }
+}
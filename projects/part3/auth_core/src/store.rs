@@ -0,0 +1,99 @@
+use std::{collections::HashMap, path::PathBuf};
+use crate::{hash_password, is_valid_hash, Config, LoginAction, LoginError, User};
+
+/// Somewhere that users are kept. Implement this to plug a different
+/// backend into `login_with_store`.
+pub trait UserStore {
+    fn get_users(&self) -> Result<HashMap<String, User>, LoginError>;
+
+    fn save_users(&self, users: &HashMap<String, User>) -> Result<(), LoginError>;
+
+    /// Checks a username and password. The default implementation looks the
+    /// user up in `get_users` and compares password hashes; stores that can
+    /// verify credentials themselves can override it.
+    fn check_credentials(&self, username: &str, password: &str) -> Result<LoginAction, LoginError> {
+        let users = self.get_users()?;
+        let password = hash_password(password);
+
+        // Usernames are stored lowercase (see `User::new`)
+        if let Some(user) = users.get(&username.to_lowercase()) {
+            if !is_valid_hash(&user.password) {
+                return Err(LoginError::InvalidHash(user.username.clone()));
+            }
+            if user.password == password {
+                Ok(LoginAction::Granted(user.role.clone()))
+            } else {
+                Ok(LoginAction::Denied)
+            }
+        } else {
+            // Don't reveal which usernames exist
+            Ok(LoginAction::Denied)
+        }
+    }
+}
+
+/// Stores users in a JSON file, `users.json` by default. If the file
+/// doesn't exist, it is created from the `Config` seed users.
+pub struct JsonFileStore {
+    path: PathBuf,
+}
+
+impl JsonFileStore {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl Default for JsonFileStore {
+    fn default() -> Self {
+        Self::new("users.json")
+    }
+}
+
+fn get_default_users() -> Result<HashMap<String, User>, LoginError> {
+    let config = Config::load()?;
+    let users = config
+        .seed_users()
+        .into_iter()
+        .map(|user| (user.username.clone(), user))
+        .collect();
+    Ok(users)
+}
+
+/// Parses the contents of a users file.
+pub fn users_from_json(users_json: &str) -> Result<HashMap<String, User>, LoginError> {
+    Ok(serde_json::from_str(users_json)?)
+}
+
+pub fn users_to_json(users: &HashMap<String, User>) -> Result<String, LoginError> {
+    Ok(serde_json::to_string(users)?)
+}
+
+impl UserStore for JsonFileStore {
+    fn get_users(&self) -> Result<HashMap<String, User>, LoginError> {
+        if self.path.exists() {
+            // Load the file
+            let users_json = std::fs::read_to_string(&self.path)?;
+            users_from_json(&users_json)
+        } else {
+            // Create a file and return it
+            let users = get_default_users()?;
+            self.save_users(&users)?;
+            Ok(users)
+        }
+    }
+
+    fn save_users(&self, users: &HashMap<String, User>) -> Result<(), LoginError> {
+        let users_json = users_to_json(users)?;
+        std::fs::write(&self.path, users_json)?;
+        Ok(())
+    }
+}
+
+pub fn get_users() -> Result<HashMap<String, User>, LoginError> {
+    JsonFileStore::default().get_users()
+}
+
+pub fn save_users(users: &HashMap<String, User>) -> Result<(), LoginError> {
+    JsonFileStore::default().save_users(users)
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "audit")]
+
+use auth_core::{login_with_store, JsonFileStore, LoginAction, LoginRole, User, UserStore};
+
+// This runs in its own process, so pointing AUDIT_LOG somewhere that can't
+// be written doesn't affect the other tests
+#[test]
+fn login_works_when_the_audit_log_cannot_be_written() {
+    let dir = std::env::temp_dir();
+    std::env::set_var("AUDIT_LOG", dir.join("auth_core_no_such_dir").join("audit.jsonl"));
+
+    let path = dir.join(format!("auth_core_audit_failure_{}.json", std::process::id()));
+    let store = JsonFileStore::new(&path);
+    let bob = User::new("bob", "Passw0rd", LoginRole::User);
+    store.save_users(&[(bob.username.clone(), bob)].into_iter().collect()).unwrap();
+
+    let login = |password| login_with_store(&store, "bob", password, "test").unwrap();
+    assert_eq!(login("Passw0rd"), LoginAction::Granted(LoginRole::User));
+    assert_eq!(login("wrong"), LoginAction::Denied);
+    std::fs::remove_file(path).unwrap();
+}
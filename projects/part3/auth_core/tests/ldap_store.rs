@@ -0,0 +1,88 @@
+#![cfg(feature = "ldap")]
+
+mod mock_ldap;
+
+use auth_core::{LdapConfig, LdapStore, LoginAction, LoginError, LoginRole, UserStore};
+use mock_ldap::MockEntry;
+use std::time::Duration;
+
+const ADMINS: &str = "cn=admins,ou=groups,dc=example,dc=com";
+
+fn store() -> LdapStore {
+    let url = mock_ldap::start(vec![
+        MockEntry {
+            dn: "uid=alice,ou=people,dc=example,dc=com",
+            password: "wonderland",
+            member_of: vec!["cn=staff,ou=groups,dc=example,dc=com", ADMINS],
+        },
+        MockEntry {
+            dn: "uid=bob,ou=people,dc=example,dc=com",
+            password: "builder",
+            member_of: vec!["cn=staff,ou=groups,dc=example,dc=com"],
+        },
+    ]);
+    store_at(url)
+}
+
+fn store_at(url: String) -> LdapStore {
+    LdapStore::new(LdapConfig {
+        url,
+        user_dn_template: "uid={username},ou=people,dc=example,dc=com".to_string(),
+        admin_group: ADMINS.to_string(),
+        timeout: Duration::from_secs(2),
+    })
+}
+
+#[test]
+fn admin_group_members_are_admins() {
+    assert_eq!(
+        store().check_credentials("alice", "wonderland").unwrap(),
+        LoginAction::Granted(LoginRole::Admin)
+    );
+}
+
+#[test]
+fn other_users_are_users() {
+    assert_eq!(
+        store().check_credentials("Bob", "builder").unwrap(),
+        LoginAction::Granted(LoginRole::User)
+    );
+}
+
+#[test]
+fn wrong_password_and_unknown_user_are_denied() {
+    let store = store();
+    assert_eq!(store.check_credentials("bob", "wrong").unwrap(), LoginAction::Denied);
+    assert_eq!(store.check_credentials("carol", "builder").unwrap(), LoginAction::Denied);
+}
+
+#[test]
+fn usernames_cant_inject_dn_components() {
+    assert_eq!(
+        store()
+            .check_credentials("bob,ou=people,dc=example,dc=com", "builder")
+            .unwrap(),
+        LoginAction::Denied
+    );
+}
+
+#[test]
+fn empty_password_is_denied_without_binding() {
+    // Nothing is listening here - if we tried to bind, this would be an error
+    let store = store_at("ldap://127.0.0.1:1".to_string());
+    assert_eq!(store.check_credentials("alice", "").unwrap(), LoginAction::Denied);
+}
+
+#[test]
+fn unreachable_server_is_an_error() {
+    let store = store_at("ldap://127.0.0.1:1".to_string());
+    assert!(matches!(
+        store.check_credentials("alice", "wonderland"),
+        Err(LoginError::Directory(_))
+    ));
+}
+
+#[test]
+fn users_cant_be_listed() {
+    assert!(matches!(store().get_users(), Err(LoginError::Directory(_))));
+}
@@ -0,0 +1,153 @@
+//! Just enough of an LDAP server to test `LdapStore` against: simple binds,
+//! base-scope searches and unbinds, speaking the BER wire format by hand.
+
+use std::{
+    io::{Read, Write},
+    net::{TcpListener, TcpStream},
+};
+
+pub struct MockEntry {
+    pub dn: &'static str,
+    pub password: &'static str,
+    pub member_of: Vec<&'static str>,
+}
+
+const SUCCESS: u8 = 0;
+const NO_SUCH_OBJECT: u8 = 32;
+const INVALID_CREDENTIALS: u8 = 49;
+const INSUFFICIENT_ACCESS: u8 = 50;
+
+/// Starts the server on a spare port and returns its `ldap://` URL. It
+/// runs until the test process exits.
+pub fn start(entries: Vec<MockEntry>) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("ldap://{}", listener.local_addr().unwrap());
+    let entries: &'static [MockEntry] = entries.leak();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().flatten() {
+            std::thread::spawn(move || serve(stream, entries));
+        }
+    });
+    url
+}
+
+fn read_length(stream: &mut TcpStream) -> Option<usize> {
+    let mut byte = [0];
+    stream.read_exact(&mut byte).ok()?;
+    if byte[0] < 0x80 {
+        return Some(byte[0] as usize);
+    }
+    let mut len = 0;
+    for _ in 0..(byte[0] & 0x7f) {
+        stream.read_exact(&mut byte).ok()?;
+        len = (len << 8) | byte[0] as usize;
+    }
+    Some(len)
+}
+
+/// Splits BER content into (tag, value) pairs
+fn parse(mut bytes: &[u8]) -> Vec<(u8, &[u8])> {
+    let mut items = Vec::new();
+    while bytes.len() >= 2 {
+        let tag = bytes[0];
+        let (len, header) = if bytes[1] < 0x80 {
+            (bytes[1] as usize, 2)
+        } else {
+            let n = (bytes[1] & 0x7f) as usize;
+            let len = bytes[2..2 + n].iter().fold(0, |len, b| (len << 8) | *b as usize);
+            (len, 2 + n)
+        };
+        items.push((tag, &bytes[header..header + len]));
+        bytes = &bytes[header + len..];
+    }
+    items
+}
+
+fn tlv(tag: u8, content: &[u8]) -> Vec<u8> {
+    let mut out = vec![tag];
+    match content.len() {
+        len @ 0..=0x7f => out.push(len as u8),
+        len @ 0x80..=0xff => out.extend([0x81, len as u8]),
+        len => out.extend([0x82, (len >> 8) as u8, len as u8]),
+    }
+    out.extend_from_slice(content);
+    out
+}
+
+fn message(id: &[u8], op: Vec<u8>) -> Vec<u8> {
+    let mut content = tlv(0x02, id);
+    content.extend(op);
+    tlv(0x30, &content)
+}
+
+// resultCode, matchedDN, diagnosticMessage
+fn result(tag: u8, code: u8) -> Vec<u8> {
+    let mut content = tlv(0x0a, &[code]);
+    content.extend(tlv(0x04, b""));
+    content.extend(tlv(0x04, b""));
+    tlv(tag, &content)
+}
+
+fn find<'a>(entries: &'a [MockEntry], dn: &[u8]) -> Option<&'a MockEntry> {
+    let dn = String::from_utf8_lossy(dn);
+    entries.iter().find(|entry| entry.dn.eq_ignore_ascii_case(&dn))
+}
+
+fn serve(mut stream: TcpStream, entries: &[MockEntry]) {
+    let mut bound = false;
+    loop {
+        let mut tag = [0];
+        if stream.read_exact(&mut tag).is_err() || tag[0] != 0x30 {
+            return;
+        }
+        let Some(len) = read_length(&mut stream) else {
+            return;
+        };
+        let mut body = vec![0; len];
+        if stream.read_exact(&mut body).is_err() {
+            return;
+        }
+        let items = parse(&body);
+        let (id, (op, request)) = (items[0].1, items[1]);
+
+        let reply = match op {
+            // BindRequest: version, name, [0] simple password
+            0x60 => {
+                let fields = parse(request);
+                let password = fields[2].1;
+                bound = find(entries, fields[1].1)
+                    .is_some_and(|entry| entry.password.as_bytes() == password);
+                let code = if bound { SUCCESS } else { INVALID_CREDENTIALS };
+                message(id, result(0x61, code))
+            }
+            // UnbindRequest
+            0x42 => return,
+            // SearchRequest: baseObject comes first
+            0x63 => {
+                let base = parse(request)[0].1;
+                match find(entries, base) {
+                    Some(entry) if bound => {
+                        let values: Vec<u8> = entry
+                            .member_of
+                            .iter()
+                            .flat_map(|group| tlv(0x04, group.as_bytes()))
+                            .collect();
+                        let mut attribute = tlv(0x04, b"memberOf");
+                        attribute.extend(tlv(0x31, &values));
+                        let mut found = tlv(0x04, entry.dn.as_bytes());
+                        found.extend(tlv(0x30, &tlv(0x30, &attribute)));
+                        let mut reply = message(id, tlv(0x64, &found));
+                        reply.extend(message(id, result(0x65, SUCCESS)));
+                        reply
+                    }
+                    Some(_) => message(id, result(0x65, INSUFFICIENT_ACCESS)),
+                    None => message(id, result(0x65, NO_SUCH_OBJECT)),
+                }
+            }
+            _ => return,
+        };
+        if stream.write_all(&reply).is_err() {
+            return;
+        }
+    }
+}
@@ -0,0 +1,108 @@
+use auth_core::{
+    login_with_store, users_from_json, users_to_json, JsonFileStore, LoginAction, LoginRole,
+    Permission, Permissions, User, UserStore,
+};
+use proptest::prelude::*;
+use std::{collections::HashMap, path::PathBuf};
+
+// Logins are audited in the temp directory rather than the crate
+fn use_temp_audit_log() {
+    static ONCE: std::sync::Once = std::sync::Once::new();
+    ONCE.call_once(|| {
+        let path = std::env::temp_dir().join(format!("auth_core_audit_{}.jsonl", std::process::id()));
+        std::env::set_var("AUDIT_LOG", path);
+    });
+}
+
+// Each test gets its own file, since the tests run in parallel
+fn temp_store(name: &str, users: &[User]) -> (JsonFileStore, PathBuf) {
+    let path = std::env::temp_dir().join(format!("auth_core_{name}_{}.json", std::process::id()));
+    let store = JsonFileStore::new(&path);
+    let users = users
+        .iter()
+        .map(|user| (user.username.clone(), user.clone()))
+        .collect();
+    store.save_users(&users).unwrap();
+    (store, path)
+}
+
+fn auditor() -> LoginRole {
+    LoginRole::Custom {
+        name: "auditor".to_string(),
+        permissions: Permissions::default().with(Permission::ReadReports),
+    }
+}
+
+fn sample_users() -> Vec<User> {
+    vec![
+        User::new("admin", "password", LoginRole::Admin),
+        User::new("bob", "password", LoginRole::User),
+        User::new("Carol", "password", auditor()),
+    ]
+}
+
+fn role() -> impl Strategy<Value = LoginRole> {
+    prop_oneof![
+        Just(LoginRole::Admin),
+        Just(LoginRole::User),
+        (any::<String>(), prop::collection::vec(prop::sample::select(Permission::ALL.to_vec()), 0..4))
+            .prop_map(|(name, permissions)| LoginRole::Custom {
+                name,
+                permissions: permissions.into_iter().collect(),
+            }),
+    ]
+}
+
+fn user() -> impl Strategy<Value = User> {
+    // Not `User::new`, so the file can hold anything, not just what we write
+    (any::<String>(), any::<String>(), role()).prop_map(|(username, password, role)| User {
+        username,
+        password,
+        role,
+    })
+}
+
+/// Flips the case of the letters where `upper` says so.
+fn recase(name: &str, upper: &[bool]) -> String {
+    name.chars()
+        .zip(upper.iter().chain(std::iter::repeat(&false)))
+        .map(|(c, upper)| if *upper { c.to_ascii_uppercase() } else { c })
+        .collect()
+}
+
+proptest! {
+    #[test]
+    fn login_never_panics(username in any::<String>(), password in any::<String>()) {
+        use_temp_audit_log();
+        let (store, path) = temp_store("never_panics", &sample_users());
+        let result = login_with_store(&store, &username, &password, "proptest");
+        std::fs::remove_file(path).unwrap();
+        prop_assert!(result.is_ok());
+    }
+
+    #[test]
+    fn roles_ignore_username_case(
+        index in 0..3usize,
+        upper in prop::collection::vec(any::<bool>(), 5),
+    ) {
+        let users = sample_users();
+        let (store, path) = temp_store("username_case", &users);
+        let username = recase(&users[index].username, &upper);
+        let result = store.check_credentials(&username, "password");
+        std::fs::remove_file(path).unwrap();
+        prop_assert_eq!(result.unwrap(), LoginAction::Granted(users[index].role.clone()));
+    }
+
+    #[test]
+    fn user_file_round_trips(users in prop::collection::hash_map(any::<String>(), user(), 0..8)) {
+        let json = users_to_json(&users).unwrap();
+        let loaded: HashMap<String, User> = users_from_json(&json).unwrap();
+        prop_assert_eq!(loaded.len(), users.len());
+        for (key, user) in users.iter() {
+            let copy = &loaded[key];
+            prop_assert_eq!(&copy.username, &user.username);
+            prop_assert_eq!(&copy.password, &user.password);
+            prop_assert_eq!(&copy.role, &user.role);
+        }
+    }
+}
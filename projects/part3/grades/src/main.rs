@@ -0,0 +1,33 @@
+use anyhow::Context;
+use grades_lib::{average, load_grades, GradesError};
+
+// The library's errors are typed so it can be precise. Out here we only
+// need to explain what happened, so anyhow collects everything, and
+// `context` says what we were doing at the time.
+fn run(path: &str) -> anyhow::Result<()> {
+    let grades = load_grades(path).with_context(|| format!("Unable to load grades from {path}"))?;
+    for grade in &grades {
+        println!("{:<10} {:>3}", grade.name, grade.score);
+    }
+    println!("Average: {:.1}", average(&grades));
+    Ok(())
+}
+
+// Try `cargo run -- bad_grades.txt` and `cargo run -- missing.txt`
+fn main() {
+    let path = std::env::args().nth(1).unwrap_or_else(|| "grades.txt".to_string());
+
+    if let Err(e) = run(&path) {
+        // `{e}` is just the outermost message; the chain has every cause
+        println!("Error: {e}");
+        for cause in e.chain().skip(1) {
+            println!("  caused by: {cause}");
+        }
+
+        // The typed error is still in there if we need to act on it
+        if let Some(GradesError::Io(_)) = e.downcast_ref::<GradesError>() {
+            println!("Check the file name and try again.");
+        }
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,136 @@
+use std::{num::ParseIntError, path::Path};
+use thiserror::Error;
+
+#[derive(Debug, PartialEq)]
+pub struct Grade {
+    pub name: String,
+    pub score: u32,
+}
+
+/// What can go wrong with a single line. Each variant is something the
+/// caller might want to handle differently, so they're typed rather than
+/// just strings.
+#[derive(Debug, Error, PartialEq)]
+pub enum ParseError {
+    #[error("expected `name,score`")]
+    MissingComma,
+    #[error("the name is empty")]
+    EmptyName,
+    // #[from] writes `impl From<ParseIntError> for ParseError`, which is
+    // what lets `?` convert it
+    #[error("the score isn't a whole number")]
+    BadScore(#[from] ParseIntError),
+    #[error("a score of {0} is over 100")]
+    OutOfRange(u32),
+}
+
+/// What can go wrong with the whole file.
+#[derive(Debug, Error)]
+pub enum GradesError {
+    #[error("unable to read the grades file")]
+    Io(#[from] std::io::Error),
+    // #[source] keeps the ParseError as the cause, so callers can still
+    // get at it
+    #[error("line {line} is invalid")]
+    Parse {
+        line: usize,
+        #[source]
+        source: ParseError,
+    },
+    #[error("the file doesn't contain any grades")]
+    Empty,
+}
+
+pub fn parse_line(line: &str) -> Result<Grade, ParseError> {
+    let (name, score) = line.split_once(',').ok_or(ParseError::MissingComma)?;
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(ParseError::EmptyName);
+    }
+    let score: u32 = score.trim().parse()?;
+    if score > 100 {
+        return Err(ParseError::OutOfRange(score));
+    }
+    Ok(Grade {
+        name: name.to_string(),
+        score,
+    })
+}
+
+/// Parses every non-blank line, stopping at the first bad one.
+pub fn parse_grades(text: &str) -> Result<Vec<Grade>, GradesError> {
+    let grades = text
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(n, line)| {
+            parse_line(line).map_err(|source| GradesError::Parse { line: n + 1, source })
+        })
+        // Collecting into a Result stops at the first Err
+        .collect::<Result<Vec<_>, _>>()?;
+    if grades.is_empty() {
+        return Err(GradesError::Empty);
+    }
+    Ok(grades)
+}
+
+pub fn load_grades(path: impl AsRef<Path>) -> Result<Vec<Grade>, GradesError> {
+    // io::Error becomes GradesError::Io through its From impl
+    let text = std::fs::read_to_string(path)?;
+    parse_grades(&text)
+}
+
+pub fn average(grades: &[Grade]) -> f64 {
+    grades.iter().map(|g| g.score as f64).sum::<f64>() / grades.len() as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_a_line() {
+        assert_eq!(
+            parse_line(" Alice , 92 "),
+            Ok(Grade {
+                name: "Alice".to_string(),
+                score: 92
+            })
+        );
+    }
+
+    #[test]
+    fn each_line_error() {
+        assert_eq!(parse_line("Alice 92"), Err(ParseError::MissingComma));
+        assert_eq!(parse_line(",92"), Err(ParseError::EmptyName));
+        assert!(matches!(parse_line("Alice,ninety"), Err(ParseError::BadScore(_))));
+        assert_eq!(parse_line("Alice,120"), Err(ParseError::OutOfRange(120)));
+    }
+
+    #[test]
+    fn file_errors_keep_the_line_and_cause() {
+        let err = parse_grades("Alice,92\n\nBob,lots").unwrap_err();
+        match &err {
+            GradesError::Parse { line, source } => {
+                assert_eq!(*line, 3);
+                assert!(matches!(source, ParseError::BadScore(_)));
+            }
+            other => panic!("Unexpected error: {other}"),
+        }
+        let source = std::error::Error::source(&err).unwrap();
+        assert_eq!(source.to_string(), "the score isn't a whole number");
+    }
+
+    #[test]
+    fn empty_file() {
+        assert!(matches!(parse_grades("\n  \n"), Err(GradesError::Empty)));
+    }
+
+    #[test]
+    fn missing_file() {
+        assert!(matches!(
+            load_grades("no_such_grades.txt"),
+            Err(GradesError::Io(_))
+        ));
+    }
+}
@@ -1,33 +1,256 @@
-use login_lib_hash::{read_line, login, LoginAction, LoginRole};
+use clap::Parser;
+use login_lib_hash::{
+    change_password, prompt_with_validator, read_line, read_password, login, register, Limit,
+    LoginAction, LoginError, LoginRole, Messages, PasswordChange, RateLimiter, RegistrationError,
+};
+use std::time::Duration;
+use serde::Serialize;
 
-fn main() {
+/// Log in interactively, or pass --username to log in once from a script.
+/// Pass --passwd to change a password, or --register to create an account.
+///
+/// Exit codes: 0 granted, 1 denied, 2 locked out, 3 error.
+#[derive(Parser)]
+#[command()]
+struct Args {
+    /// Log in as this user without prompting for a username
+    #[arg(long)]
+    username: Option<String>,
+
+    /// Read the password from the first line of stdin
+    #[arg(long, requires = "username")]
+    password_stdin: bool,
+
+    /// Print the result as JSON. Nothing else is printed, so there can be
+    /// no prompts: the username and password must be passed in.
+    #[arg(long, requires_all = ["username", "password_stdin"])]
+    json: bool,
+
+    /// Change a password instead of logging in
+    #[arg(long, conflicts_with_all = ["password_stdin", "json"])]
+    passwd: bool,
+
+    /// Create a new account instead of logging in
+    #[arg(long, conflicts_with_all = ["password_stdin", "json", "passwd"])]
+    register: bool,
+}
+
+enum Outcome {
+    Granted(LoginRole),
+    Denied,
+    Locked,
+    Error(LoginError),
+}
+
+impl Outcome {
+    fn exit_code(&self) -> i32 {
+        match self {
+            Outcome::Granted(_) => 0,
+            Outcome::Denied => 1,
+            Outcome::Locked => 2,
+            Outcome::Error(_) => 3,
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    username: &'a str,
+    outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    role: Option<&'a LoginRole>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+fn interactive(messages: &Messages) -> (String, Outcome) {
+    // Two quick tries, then each one after that has to wait its turn
+    let mut limiter = RateLimiter::new(
+        Limit::new(2, Duration::from_secs(5)),
+        Limit::new(2, Duration::from_secs(5)),
+    );
     let mut tries = 0;
     loop {
-        println!("Enter your username:");
-        let username = read_line();
-        println!("Enter your password:");
-        let password = read_line();
+        let username = username_prompt(messages);
+        while let Err(limited) = limiter.check(&username, "console") {
+            let seconds = limited.retry_after_secs();
+            println!("{}", messages.get("rate-limited", &[("seconds", &seconds)]));
+            std::thread::sleep(limited.retry_after);
+        }
+        println!("{}", messages.get("prompt-password", &[]));
+        let password = read_password();
         match login(&username, &password) {
-            Some(LoginAction::Granted(LoginRole::Admin)) => {
-                println!("Welcome {username}, you are an admin.");
-                break;
-            }
-            Some(LoginAction::Granted(LoginRole::User)) => {
-                println!("Welcome {username}, you are a regular user.");
-                break
-            }
-            Some(LoginAction::Denied) => {
-                println!("Login failed.");
+            Ok(LoginAction::Granted(role)) => return (username, Outcome::Granted(role)),
+            Ok(LoginAction::Denied) => {
+                println!("{}", messages.get("login-failed", &[]));
                 tries += 1;
                 if tries >= 3 {
-                    println!("Too many failed attempts. Exiting.");
-                    break;
+                    return (username, Outcome::Locked);
                 }
             }
-            None => {
-                println!("User does not exist.");
-                break;
+            Err(e) => return (username, Outcome::Error(e)),
+        }
+    }
+}
+
+fn print_report(messages: &Messages, username: &str, outcome: &Outcome) {
+    let user = [("username", &username as &dyn std::fmt::Display)];
+    let message = match outcome {
+        Outcome::Granted(LoginRole::Admin) => messages.get("login-admin", &user),
+        Outcome::Granted(LoginRole::User) => messages.get("login-user", &user),
+        Outcome::Granted(role @ LoginRole::Custom { .. }) => {
+            println!("{}", messages.get("login-custom", &[user[0], ("role", role)]));
+            messages.get("login-permissions", &[("permissions", &role.permissions())])
+        }
+        Outcome::Denied => messages.get("login-failed", &[]),
+        Outcome::Locked => messages.get("login-locked", &[]),
+        Outcome::Error(LoginError::Io(_)) => messages.get("error-io", &[]),
+        Outcome::Error(LoginError::CorruptUserFile(_)) => messages.get("error-corrupt", &[]),
+        Outcome::Error(LoginError::InvalidHash(_)) => messages.get("error-invalid-hash", &[]),
+        Outcome::Error(LoginError::Config(err)) => messages.get("error-config", &[("error", err)]),
+        Outcome::Error(LoginError::Directory(_)) => messages.get("error-directory", &[]),
+    };
+    println!("{message}");
+}
+
+fn print_json_report(username: &str, outcome: &Outcome) {
+    let report = JsonReport {
+        username,
+        outcome: match outcome {
+            Outcome::Granted(_) => "granted",
+            Outcome::Denied => "denied",
+            Outcome::Locked => "locked",
+            Outcome::Error(_) => "error",
+        },
+        role: match outcome {
+            Outcome::Granted(role) => Some(role),
+            _ => None,
+        },
+        error: match outcome {
+            Outcome::Error(e) => Some(e.to_string()),
+            _ => None,
+        },
+    };
+    println!("{}", serde_json::to_string(&report).unwrap());
+}
+
+fn username_prompt(messages: &Messages) -> String {
+    let username = prompt_with_validator(&messages.get("prompt-username", &[]), |name: &String| {
+        if name.is_empty() {
+            Err(messages.get("username-empty", &[]))
+        } else {
+            Ok(())
+        }
+    });
+    // Nothing left to read (e.g. stdin is /dev/null), so give up
+    username.unwrap_or_else(|_| std::process::exit(3))
+}
+
+fn passwd(messages: &Messages, username: Option<String>) -> i32 {
+    let username = username.unwrap_or_else(|| username_prompt(messages));
+    println!("{}", messages.get("passwd-current", &[]));
+    let old_password = read_password();
+    println!("{}", messages.get("passwd-new", &[]));
+    let new_password = read_password();
+    println!("{}", messages.get("passwd-retype", &[]));
+    if read_password() != new_password {
+        println!("{}", messages.get("passwd-mismatch", &[]));
+        return 1;
+    }
+
+    match change_password(&username, &old_password, &new_password) {
+        Ok(PasswordChange::Changed) => {
+            println!("{}", messages.get("passwd-changed", &[("username", &username)]));
+            0
+        }
+        Ok(PasswordChange::Denied) => {
+            println!("{}", messages.get("passwd-denied", &[]));
+            1
+        }
+        Ok(PasswordChange::Rejected(violations)) => {
+            println!("{}", messages.get("passwd-rejected", &[]));
+            for violation in violations {
+                println!("{}", messages.violation(&violation));
             }
+            1
+        }
+        Err(e) => {
+            print_report(messages, &username, &Outcome::Error(e));
+            3
+        }
+    }
+}
+
+fn register_account(messages: &Messages, username: Option<String>) -> i32 {
+    let username = username.unwrap_or_else(|| username_prompt(messages));
+    println!("{}", messages.get("register-password", &[]));
+    let password = read_password();
+    println!("{}", messages.get("register-retype", &[]));
+    if read_password() != password {
+        println!("{}", messages.get("register-mismatch", &[]));
+        return 1;
+    }
+
+    match register(&username, &password) {
+        Ok(user) => {
+            println!("{}", messages.get("register-created", &[("username", &user.username)]));
+            0
+        }
+        Err(RegistrationError::EmptyUsername) => {
+            println!("{}", messages.get("username-empty", &[]));
+            1
         }
+        Err(RegistrationError::UsernameTaken(username)) => {
+            println!("{}", messages.get("register-taken", &[("username", &username)]));
+            1
+        }
+        Err(RegistrationError::WeakPassword(violations)) => {
+            println!("{}", messages.get("register-rejected", &[]));
+            for violation in violations {
+                println!("{}", messages.violation(&violation));
+            }
+            1
+        }
+        Err(RegistrationError::Login(e)) => {
+            print_report(messages, &username, &Outcome::Error(e));
+            3
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+    telemetry::init("warn");
+    let messages = Messages::from_env();
+
+    if args.passwd {
+        std::process::exit(passwd(&messages, args.username));
+    }
+    if args.register {
+        std::process::exit(register_account(&messages, args.username));
+    }
+
+    let (username, outcome) = if let Some(username) = args.username {
+        let password = if args.password_stdin {
+            read_line()
+        } else {
+            println!("{}", messages.get("prompt-password", &[]));
+            read_password()
+        };
+        let outcome = match login(&username, &password) {
+            Ok(LoginAction::Granted(role)) => Outcome::Granted(role),
+            Ok(LoginAction::Denied) => Outcome::Denied,
+            Err(e) => Outcome::Error(e),
+        };
+        (username, outcome)
+    } else {
+        interactive(&messages)
+    };
+
+    if args.json {
+        print_json_report(&username, &outcome);
+    } else {
+        print_report(&messages, &username, &outcome);
     }
+    std::process::exit(outcome.exit_code());
 }
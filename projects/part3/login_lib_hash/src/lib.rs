@@ -1,97 +1,3 @@
-use std::{collections::HashMap, path::Path};
-use serde::{Serialize, Deserialize};
-
-pub fn read_line() -> String {
-    let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
-}
-
-#[derive(PartialEq, Debug, Serialize, Deserialize)]
-pub enum LoginAction {
-    Granted(LoginRole),
-    Denied,
-}
-
-#[derive(PartialEq, Debug, Clone, Copy, Serialize, Deserialize)]
-pub enum LoginRole {
-    Admin,
-    User,
-}
-
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct User {
-    pub username: String,
-    pub password: String,
-    pub role: LoginRole,
-}
-
-impl User {
-    pub fn new(username: &str, password: &str, role: LoginRole) -> User {
-        User {
-            username: username.to_lowercase(),
-            password: hash_password(password),
-            role,
-        }
-    }
-}
-
-pub fn hash_password(password: &str) -> String {
-    use sha2::Digest;
-    let mut hasher = sha2::Sha256::new();
-    hasher.update(password);
-    format!("{:X}", hasher.finalize())
-}
-
-fn get_default_users() -> HashMap<String, User> {
-    let mut users = HashMap::new();
-    users.insert("admin".to_string(), User::new("admin", "password", LoginRole::Admin));
-    users.insert("bob".to_string(), User::new("bob", "password", LoginRole::User));
-    users
-}
-
-pub fn get_users() -> HashMap<String, User> {
-    let users_path = Path::new("users.json");
-    if users_path.exists() {
-        // Load the file
-        let users_json = std::fs::read_to_string(users_path).unwrap();
-        let users: HashMap<String, User> = serde_json::from_str(&users_json).unwrap();
-        users
-    } else {
-        // Create a file and return it
-        let users = get_default_users();
-        let users_json = serde_json::to_string(&users).unwrap();
-        std::fs::write(users_path, users_json).unwrap();
-        users
-    }
-}
-
-pub fn login(username: &str, password: &str) -> Option<LoginAction> {
-    let users = get_users();
-    let password = hash_password(password);
-
-    if let Some(user) = users.get(username) {
-        if user.password == password {
-            Some(LoginAction::Granted(user.role.clone()))
-        } else {
-            Some(LoginAction::Denied)
-        }
-    } else {
-        None
-    }
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn test_enums() {
-        assert_eq!(login("admin", "password"), Some(LoginAction::Granted(LoginRole::Admin)));
-        assert_eq!(login("bob", "password"), Some(LoginAction::Granted(LoginRole::User)));
-        assert_eq!(login("admin", "wrong"), Some(LoginAction::Denied));
-        assert_eq!(login("wrong", "password"), None);
-    }
-}
+//! The hashing chapter's login library. Everything now lives in
+//! `auth_core`; this crate re-exports it so `login_hash` keeps working.
+pub use auth_core::*;
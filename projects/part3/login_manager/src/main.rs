@@ -1,4 +1,7 @@
-use login_lib_lm::{get_users, save_users, LoginRole, User, hash_password};
+use login_lib_lm::{
+    get_roles, get_users, hash_password, read_audit_log, save_roles, save_users, LoginError,
+    LoginRole, Messages, PasswordPolicy, Permission, User,
+};
 use clap::{Parser, Subcommand};
 
 #[derive(Parser)]
@@ -23,6 +26,10 @@ enum Commands {
         /// Optional - mark as an admin
         #[arg(long)]
         admin: Option<bool>,
+
+        /// Optional - assign a custom role from roles.json
+        #[arg(long, conflicts_with = "admin")]
+        role: Option<String>,
     },
     /// Delete a user
     Delete {
@@ -37,70 +44,193 @@ enum Commands {
         /// New Password
         new_password: String,
     },
+    /// List custom roles and their permissions
+    Roles,
+    /// Define (or redefine) a custom role
+    AddRole {
+        /// Role name
+        name: String,
+
+        /// Permissions granted to the role (read-reports, manage-users, shutdown)
+        permissions: Vec<Permission>,
+    },
+    /// Show recent login attempts
+    Audit {
+        /// How many events to show
+        #[arg(long, default_value_t = 20)]
+        count: usize,
+    },
 }
 
-fn delete_user(username: &str) {
-    let mut users = get_users();
-    if users.contains_key(username) {
-        users.remove(username);
-        save_users(&users);
+fn delete_user(messages: &Messages, username: &str) -> Result<(), LoginError> {
+    let mut users = get_users()?;
+    // Users are stored under their lowercased names (see `User::new`)
+    if users.remove(&username.to_lowercase()).is_some() {
+        save_users(&users)?;
     } else {
-        println!("{username} does not exist");
+        println!("{}", messages.get("manager-user-missing", &[("username", &username)]));
     }
+    Ok(())
 }
 
-fn list_users() {
-    println!("{:<20}{:<20}", "Username", "Login Action");
+fn list_users(messages: &Messages) -> Result<(), LoginError> {
+    println!(
+        "{:<20}{:<20}",
+        messages.get("header-username", &[]),
+        messages.get("header-role", &[])
+    );
     println!("{:-<40}", "");
 
-    let users = get_users();
+    let users = get_users()?;
     users.iter().for_each(|(_, user)| {
-        println!("{:<20}{:<20?}", user.username, user.role);
+        println!("{:<20}{:<20}", user.username, user.role);
+    });
+    Ok(())
+}
+
+fn list_roles(messages: &Messages) -> Result<(), LoginError> {
+    println!(
+        "{:<20}{:<40}",
+        messages.get("header-role-name", &[]),
+        messages.get("header-permissions", &[])
+    );
+    println!("{:-<60}", "");
+
+    let roles = get_roles()?;
+    roles.iter().for_each(|(name, permissions)| {
+        println!("{:<20}{:<40}", name, permissions);
+    });
+    Ok(())
+}
+
+fn add_role(name: String, permissions: Vec<Permission>) -> Result<(), LoginError> {
+    let mut roles = get_roles()?;
+    roles.insert(name, permissions.into_iter().collect());
+    save_roles(&roles)
+}
+
+fn show_audit_log(messages: &Messages, count: usize) -> Result<(), LoginError> {
+    println!(
+        "{:<12}{:<20}{:<10}{:<20}",
+        messages.get("header-timestamp", &[]),
+        messages.get("header-username", &[]),
+        messages.get("header-outcome", &[]),
+        messages.get("header-source", &[])
+    );
+    println!("{:-<62}", "");
+
+    let records = read_audit_log(count)?;
+    records.iter().for_each(|record| {
+        println!(
+            "{:<12}{:<20}{:<10?}{:<20}",
+            record.timestamp, record.username, record.outcome, record.source
+        );
     });
+    Ok(())
+}
+
+fn check_password_policy(messages: &Messages, password: &str) -> bool {
+    if let Err(violations) = PasswordPolicy::default().validate(password) {
+        println!("{}", messages.get("manager-password-rejected", &[]));
+        for violation in violations {
+            println!("{}", messages.violation(&violation));
+        }
+        false
+    } else {
+        true
+    }
 }
 
-fn add_user(username: String, password: String, admin: bool) {
-    let mut users = get_users();
-    if users.contains_key(&username) {
-        println!("{username} already exists");
-        return;
+fn add_user(
+    messages: &Messages,
+    username: String,
+    password: String,
+    admin: bool,
+    role: Option<String>,
+) -> Result<(), LoginError> {
+    let mut users = get_users()?;
+    if users.contains_key(&username.to_lowercase()) {
+        println!("{}", messages.get("manager-user-exists", &[("username", &username)]));
+        return Ok(());
+    }
+    if !check_password_policy(messages, &password) {
+        return Ok(());
     }
     let role = if admin {
         LoginRole::Admin
+    } else if let Some(name) = role {
+        let roles = get_roles()?;
+        if let Some(permissions) = roles.get(&name) {
+            LoginRole::Custom {
+                name,
+                permissions: *permissions,
+            }
+        } else {
+            println!("{}", messages.get("manager-role-missing", &[("name", &name)]));
+            return Ok(());
+        }
     } else {
         LoginRole::User
     };
     let user = User::new(&username, &password, role);
-    users.insert(username, user);
-    save_users(&users);
+    users.insert(user.username.clone(), user);
+    save_users(&users)
 }
 
-fn change_password(username: &str, password: &str) {
-    let mut users = get_users();
-    if let Some(user) = users.get_mut(username) {
+fn change_password(messages: &Messages, username: &str, password: &str) -> Result<(), LoginError> {
+    if !check_password_policy(messages, password) {
+        return Ok(());
+    }
+    let mut users = get_users()?;
+    if let Some(user) = users.get_mut(&username.to_lowercase()) {
         user.password = hash_password(password);
-        save_users(&users);
+        save_users(&users)?;
     } else {
-        println!("{username} does not exist");
+        println!("{}", messages.get("manager-user-missing", &[("username", &username)]));
     }
+    Ok(())
 }
 
 fn main() {
     let cli = Args::parse();
-    match cli.command {
-        Some(Commands::List) => list_users(),
+    telemetry::init("warn");
+    let messages = Messages::from_env();
+    let result = match cli.command {
+        Some(Commands::List) => list_users(&messages),
         Some(Commands::Add {
             username,
             password,
             admin,
-        }) => add_user(username, password, admin.is_some()),
-        Some(Commands::Delete { username }) => delete_user(&username),
+            role,
+        }) => add_user(&messages, username, password, admin.is_some(), role),
+        Some(Commands::Delete { username }) => delete_user(&messages, &username),
         Some(Commands::ChangePassword { username, new_password }) => {
-            change_password(&username, &new_password)
+            change_password(&messages, &username, &new_password)
         }
+        Some(Commands::Roles) => list_roles(&messages),
+        Some(Commands::AddRole { name, permissions }) => add_role(name, permissions),
+        Some(Commands::Audit { count }) => show_audit_log(&messages, count),
         None => {
-            println!("Run with --help to see instructions");
+            println!("{}", messages.get("manager-help", &[]));
             std::process::exit(0);
         }
+    };
+
+    if let Err(e) = result {
+        let message = match e {
+            LoginError::Io(err) => messages.get("manager-error-io", &[("error", &err)]),
+            LoginError::CorruptUserFile(err) => {
+                messages.get("manager-error-corrupt", &[("error", &err)])
+            }
+            LoginError::InvalidHash(username) => {
+                messages.get("manager-error-invalid-hash", &[("username", &username)])
+            }
+            LoginError::Config(err) => messages.get("error-config", &[("error", &err)]),
+            LoginError::Directory(err) => {
+                messages.get("manager-error-directory", &[("error", &err)])
+            }
+        };
+        println!("{message}");
+        std::process::exit(1);
     }
 }
\ No newline at end of file
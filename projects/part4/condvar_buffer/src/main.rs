@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Condvar, Mutex};
+use std::time::Duration;
+
+struct State<T> {
+    items: VecDeque<T>,
+    closed: bool,
+}
+
+/// A fixed-size queue. Producers sleep while it's full and consumers sleep
+/// while it's empty; nobody spins.
+struct BoundedBuffer<T> {
+    state: Mutex<State<T>>,
+    not_empty: Condvar,
+    not_full: Condvar,
+    capacity: usize,
+}
+
+impl<T> BoundedBuffer<T> {
+    fn new(capacity: usize) -> Self {
+        Self {
+            state: Mutex::new(State {
+                items: VecDeque::with_capacity(capacity),
+                closed: false,
+            }),
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+            capacity,
+        }
+    }
+
+    /// Waits for room, then adds the item. Hands it back if the buffer has
+    /// been closed.
+    fn push(&self, item: T) -> Result<(), T> {
+        // `wait_while` unlocks the mutex while asleep, and re-checks the
+        // condition whenever it wakes - including spurious wakeups
+        let mut state = self
+            .not_full
+            .wait_while(self.state.lock().unwrap(), |state| {
+                state.items.len() == self.capacity && !state.closed
+            })
+            .unwrap();
+        if state.closed {
+            return Err(item);
+        }
+        state.items.push_back(item);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    /// Waits for an item. Returns None once the buffer is closed and empty.
+    fn pop(&self) -> Option<T> {
+        let mut state = self
+            .not_empty
+            .wait_while(self.state.lock().unwrap(), |state| {
+                state.items.is_empty() && !state.closed
+            })
+            .unwrap();
+        let item = state.items.pop_front();
+        if item.is_some() {
+            self.not_full.notify_one();
+        }
+        item
+    }
+
+    /// The shutdown signal: wake everyone up so they can see it.
+    fn close(&self) {
+        self.state.lock().unwrap().closed = true;
+        self.not_empty.notify_all();
+        self.not_full.notify_all();
+    }
+}
+
+// park() is the simplest way to sleep until someone wakes you, but it can
+// also return for no reason - so always check a flag in a loop
+fn park_demo() {
+    let ready = AtomicBool::new(false);
+    std::thread::scope(|scope| {
+        let waiter = scope.spawn(|| {
+            println!("Waiter: parking until there's something to do");
+            while !ready.load(Ordering::Acquire) {
+                std::thread::park();
+            }
+            println!("Waiter: woken up and ready");
+        });
+
+        std::thread::sleep(Duration::from_millis(500));
+        println!("Main: setting the flag and unparking");
+        ready.store(true, Ordering::Release);
+        waiter.thread().unpark();
+    });
+}
+
+fn producer_consumer() {
+    let buffer = BoundedBuffer::new(3);
+
+    std::thread::scope(|scope| {
+        let producers: Vec<_> = (0..2)
+            .map(|p| {
+                let buffer = &buffer;
+                scope.spawn(move || {
+                    for n in 0..5 {
+                        println!("Producer {p}: pushing {n}");
+                        if buffer.push((p, n)).is_err() {
+                            break;
+                        }
+                    }
+                })
+            })
+            .collect();
+
+        for c in 0..2 {
+            let buffer = &buffer;
+            scope.spawn(move || {
+                while let Some((p, n)) = buffer.pop() {
+                    println!("Consumer {c}: got {n} from producer {p}");
+                    // Slower than the producers, so the buffer fills up
+                    std::thread::sleep(Duration::from_millis(100));
+                }
+                println!("Consumer {c}: buffer closed, exiting");
+            });
+        }
+
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        // Consumers finish what's left, then see the buffer is closed
+        buffer.close();
+    });
+}
+
+fn main() {
+    park_demo();
+    println!();
+    producer_consumer();
+}
@@ -0,0 +1,72 @@
+use crossbeam_channel::{after, bounded, select, tick, Sender};
+use std::time::{Duration, Instant};
+
+// Sends as fast as it can. The channel only holds a few messages, so once
+// the consumer falls behind `send` blocks until there's room.
+fn fast_producer(tx: Sender<u32>) {
+    let mut blocked = Duration::ZERO;
+    for n in 0.. {
+        let start = Instant::now();
+        if tx.send(n).is_err() {
+            break; // The consumer hung up
+        }
+        blocked += start.elapsed();
+    }
+    println!("Fast producer finished, spent {blocked:.1?} waiting for room");
+}
+
+fn slow_producer(tx: Sender<String>) {
+    for n in 0.. {
+        std::thread::sleep(Duration::from_millis(300));
+        if tx.send(format!("Slow message {n}")).is_err() {
+            break;
+        }
+    }
+    println!("Slow producer finished");
+}
+
+fn main() {
+    let (fast_tx, fast_rx) = bounded(4);
+    let (slow_tx, slow_rx) = bounded(4);
+    let fast = std::thread::spawn(move || fast_producer(fast_tx));
+    let slow = std::thread::spawn(move || slow_producer(slow_tx));
+
+    // Channels that deliver the time: every second, and once after 3 seconds
+    let report = tick(Duration::from_secs(1));
+    let deadline = after(Duration::from_secs(3));
+
+    let mut fast_count = 0;
+    let mut slow_count = 0;
+    loop {
+        // Waits on all of them at once and runs whichever is ready first
+        select! {
+            recv(fast_rx) -> n => {
+                let _ = n.unwrap();
+                fast_count += 1;
+                // Pretend each number takes some work
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            recv(slow_rx) -> msg => {
+                println!("{}", msg.unwrap());
+                slow_count += 1;
+            }
+            recv(report) -> _ => {
+                println!("Report: {fast_count} fast, {slow_count} slow so far");
+            }
+            recv(deadline) -> _ => {
+                println!("Time's up");
+                break;
+            }
+            default(Duration::from_millis(500)) => {
+                println!("Nothing arrived for half a second");
+            }
+        }
+    }
+
+    // Dropping the receivers makes the producers' next send fail
+    drop(fast_rx);
+    drop(slow_rx);
+    fast.join().unwrap();
+    slow.join().unwrap();
+    println!("Received {fast_count} fast and {slow_count} slow messages");
+}
@@ -0,0 +1,98 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+
+struct Account {
+    id: u32,
+    balance: Mutex<i64>,
+}
+
+// Long enough that the other thread always grabs its first lock meanwhile
+const PAUSE: Duration = Duration::from_millis(100);
+
+// Locks `from` then `to`. Run A -> B and B -> A at the same time and each
+// thread ends up holding the lock the other one needs.
+fn transfer_naive(from: &Account, to: &Account, amount: i64) {
+    let mut from_balance = from.balance.lock().unwrap();
+    std::thread::sleep(PAUSE);
+    let mut to_balance = to.balance.lock().unwrap();
+    *from_balance -= amount;
+    *to_balance += amount;
+}
+
+// Every thread locks the lower id first, so nobody can hold the second
+// lock while waiting for the first
+fn transfer_ordered(from: &Account, to: &Account, amount: i64) {
+    let (first, second) = if from.id < to.id { (from, to) } else { (to, from) };
+    let mut first_balance = first.balance.lock().unwrap();
+    std::thread::sleep(PAUSE);
+    let mut second_balance = second.balance.lock().unwrap();
+    if first.id == from.id {
+        *first_balance -= amount;
+        *second_balance += amount;
+    } else {
+        *first_balance += amount;
+        *second_balance -= amount;
+    }
+}
+
+// Don't wait for the second lock: if it's taken, let go of the first and
+// try again later. The backoff differs per account, or the two threads can
+// keep colliding in lockstep (a livelock).
+fn transfer_try_lock(from: &Account, to: &Account, amount: i64) {
+    let mut attempt = 1;
+    loop {
+        let mut from_balance = from.balance.lock().unwrap();
+        std::thread::sleep(PAUSE);
+        if let Ok(mut to_balance) = to.balance.try_lock() {
+            *from_balance -= amount;
+            *to_balance += amount;
+            return;
+        }
+        drop(from_balance);
+        println!("Account {} backing off (attempt {attempt})", from.id);
+        std::thread::sleep(PAUSE * attempt * from.id);
+        attempt += 1;
+    }
+}
+
+fn main() {
+    let mode = std::env::args().nth(1).unwrap_or_else(|| "deadlock".to_string());
+    let transfer = match mode.as_str() {
+        "deadlock" => transfer_naive,
+        "ordered" => transfer_ordered,
+        "try-lock" => transfer_try_lock,
+        _ => {
+            println!("Usage: lock_ordering [deadlock|ordered|try-lock]");
+            return;
+        }
+    };
+    println!("Transferring in both directions with {mode}");
+
+    let a = Account { id: 1, balance: Mutex::new(100) };
+    let b = Account { id: 2, balance: Mutex::new(100) };
+    let finished = AtomicUsize::new(0);
+
+    std::thread::scope(|scope| {
+        scope.spawn(|| {
+            transfer(&a, &b, 10);
+            finished.fetch_add(1, Ordering::Relaxed);
+        });
+        scope.spawn(|| {
+            transfer(&b, &a, 20);
+            finished.fetch_add(1, Ordering::Relaxed);
+        });
+
+        // Nothing reports a deadlock for you - the threads just stop
+        std::thread::sleep(Duration::from_secs(2));
+        if finished.load(Ordering::Relaxed) < 2 {
+            println!("Deadlocked: each thread is waiting for the other's lock. Press Ctrl+C to quit.");
+        }
+    });
+
+    println!(
+        "A: {}, B: {}",
+        a.balance.lock().unwrap(),
+        b.balance.lock().unwrap()
+    );
+}
@@ -0,0 +1,260 @@
+//! A Treiber stack: a linked list whose head is swapped with
+//! compare-and-swap, so pushes and pops never take a lock.
+//!
+//! Freeing a popped node is the hard part. Another thread may have read
+//! the same head a moment earlier and still be about to look at its
+//! `next`, and if the allocator hands the address out again its CAS can
+//! succeed against a different node (the ABA problem). Real
+//! implementations use hazard pointers or epochs (see `crossbeam-epoch`);
+//! this one keeps popped nodes on a retired list until the stack is dropped.
+
+use std::mem::ManuallyDrop;
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
+
+struct Node<T> {
+    // Moved out by `pop`, so it mustn't be dropped with the node
+    value: ManuallyDrop<T>,
+    // Never changes once the node has been pushed
+    next: *mut Node<T>,
+    next_retired: *mut Node<T>,
+}
+
+pub struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+    retired: AtomicPtr<Node<T>>,
+}
+
+// Values move between threads through the stack, so T has to be Send
+unsafe impl<T: Send> Send for TreiberStack<T> {}
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T> TreiberStack<T> {
+    pub fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            retired: AtomicPtr::new(ptr::null_mut()),
+        }
+    }
+
+    pub fn push(&self, value: T) {
+        let node = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new(value),
+            next: ptr::null_mut(),
+            next_retired: ptr::null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            // Nobody else can see the node until the CAS succeeds
+            unsafe { (*node).next = head };
+            // Release publishes the node's contents to whoever pops it
+            match self
+                .head
+                .compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed)
+            {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn pop(&self) -> Option<T> {
+        let mut head = self.head.load(Ordering::Acquire);
+        loop {
+            if head.is_null() {
+                return None;
+            }
+            // Safe to read even if another thread pops it first: nodes
+            // aren't freed until the stack is
+            let next = unsafe { (*head).next };
+            match self
+                .head
+                .compare_exchange_weak(head, next, Ordering::Acquire, Ordering::Acquire)
+            {
+                Ok(_) => {
+                    // We won the CAS, so nobody else will take the value
+                    let value = unsafe { ManuallyDrop::take(&mut (*head).value) };
+                    self.retire(head);
+                    return Some(value);
+                }
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire).is_null()
+    }
+
+    // The retired list is a push-only Treiber stack of its own
+    fn retire(&self, node: *mut Node<T>) {
+        let mut retired = self.retired.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*node).next_retired = retired };
+            match self.retired.compare_exchange_weak(
+                retired,
+                node,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => retired = current,
+            }
+        }
+    }
+}
+
+impl<T> Default for TreiberStack<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        // &mut self means no other thread is using the stack any more
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            let mut boxed = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut boxed.value) };
+            node = boxed.next;
+        }
+
+        // Retired nodes have already given up their values
+        let mut node = *self.retired.get_mut();
+        while !node.is_null() {
+            let boxed = unsafe { Box::from_raw(node) };
+            node = boxed.next_retired;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    #[test]
+    fn last_in_first_out() {
+        let stack = TreiberStack::new();
+        stack.push(1);
+        stack.push(2);
+        stack.push(3);
+        assert_eq!(stack.pop(), Some(3));
+        assert_eq!(stack.pop(), Some(2));
+        assert_eq!(stack.pop(), Some(1));
+        assert_eq!(stack.pop(), None);
+        assert!(stack.is_empty());
+    }
+
+    #[test]
+    fn every_value_is_popped_exactly_once() {
+        let stack = TreiberStack::new();
+        let popped: Vec<u32> = std::thread::scope(|scope| {
+            for t in 0..4 {
+                let stack = &stack;
+                scope.spawn(move || {
+                    for n in 0..1000 {
+                        stack.push(t * 1000 + n);
+                    }
+                });
+            }
+            let poppers: Vec<_> = (0..4)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut mine = Vec::new();
+                        for _ in 0..2000 {
+                            if let Some(n) = stack.pop() {
+                                mine.push(n);
+                            }
+                        }
+                        mine
+                    })
+                })
+                .collect();
+            poppers
+                .into_iter()
+                .flat_map(|h| h.join().unwrap())
+                .collect()
+        });
+
+        let mut seen: HashSet<u32> = popped.iter().copied().collect();
+        assert_eq!(seen.len(), popped.len(), "a value was popped twice");
+        while let Some(n) = stack.pop() {
+            assert!(seen.insert(n), "a value was popped twice");
+        }
+        assert_eq!(seen.len(), 4000);
+    }
+
+    #[test]
+    fn dropping_the_stack_drops_its_values() {
+        let value = Arc::new(());
+        let stack = TreiberStack::new();
+        for _ in 0..3 {
+            stack.push(value.clone());
+        }
+        drop(stack.pop());
+        assert_eq!(Arc::strong_count(&value), 3);
+        drop(stack);
+        assert_eq!(Arc::strong_count(&value), 1);
+    }
+
+    // The ABA problem, played out by hand on a stack that frees and
+    // reuses nodes. Thread 1 reads head = A (next = B) and is paused.
+    // Thread 2 pops A, pops B, then pushes A's memory back. Head is A
+    // again, so thread 1's CAS succeeds - and makes the popped B the head.
+    #[test]
+    fn aba_breaks_a_stack_that_reuses_nodes() {
+        let b = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new("B"),
+            next: ptr::null_mut(),
+            next_retired: ptr::null_mut(),
+        }));
+        let a = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new("A"),
+            next: b,
+            next_retired: ptr::null_mut(),
+        }));
+        let head = AtomicPtr::new(a);
+
+        // Thread 1 starts a pop
+        let seen_head = head.load(Ordering::Acquire);
+        let seen_next = unsafe { (*seen_head).next };
+
+        // Thread 2 pops A and B, then pushes a new value that happens to
+        // get A's old address
+        head.store(ptr::null_mut(), Ordering::Release);
+        unsafe {
+            (*a).value = ManuallyDrop::new("C");
+            (*a).next = ptr::null_mut();
+        }
+        head.store(a, Ordering::Release);
+
+        // Thread 1 carries on. The CAS only compares addresses, so it can't
+        // tell that A has become C
+        let result =
+            head.compare_exchange(seen_head, seen_next, Ordering::Acquire, Ordering::Acquire);
+        assert!(result.is_ok());
+        assert_eq!(head.load(Ordering::Acquire), b, "B is back, though it was popped");
+
+        unsafe {
+            drop(Box::from_raw(a));
+            drop(Box::from_raw(b));
+        }
+    }
+
+    // Retiring nodes means an address can't come back while the stack is
+    // alive, which is what makes the CAS above trustworthy
+    #[test]
+    fn popped_nodes_are_not_reused() {
+        let stack = TreiberStack::new();
+        stack.push("A");
+        let a = stack.head.load(Ordering::Acquire);
+        stack.pop();
+        for value in ["C", "D", "E"] {
+            stack.push(value);
+            assert_ne!(stack.head.load(Ordering::Acquire), a);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use lockfree_stack::TreiberStack;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+fn main() {
+    let stack = TreiberStack::new();
+    let popped = AtomicU64::new(0);
+    let total = AtomicU64::new(0);
+
+    std::thread::scope(|scope| {
+        for t in 0..4u64 {
+            let stack = &stack;
+            scope.spawn(move || {
+                for n in 0..100_000 {
+                    stack.push(t * 100_000 + n);
+                }
+            });
+        }
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..100_000 {
+                    if let Some(n) = stack.pop() {
+                        popped.fetch_add(1, Ordering::Relaxed);
+                        total.fetch_add(n, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    // Whatever the poppers missed is still on the stack
+    let mut left = 0;
+    while let Some(n) = stack.pop() {
+        left += 1;
+        total.fetch_add(n, Ordering::Relaxed);
+    }
+
+    println!("Popped {} while pushing, {left} afterwards", popped.into_inner());
+    println!("Sum of values: {} (expected {})", total.into_inner(), (0..400_000u64).sum::<u64>());
+}
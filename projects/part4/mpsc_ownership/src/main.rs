@@ -6,12 +6,13 @@ struct MyData {
     n: u32,
 }
 
-pub fn read_line() -> String {
+// None once stdin runs out, so the loop can stop
+fn read_line() -> Option<String> {
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim().to_string()),
+    }
 }
 
 fn main() {
@@ -28,7 +29,7 @@ fn main() {
     let mut n = 0;
     loop {
         println!("Enter a string");
-        let input = read_line();
+        let Some(input) = read_line() else { break };
         let data_to_move = MyData {
             data: input,
             n,
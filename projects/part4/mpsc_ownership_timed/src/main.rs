@@ -5,12 +5,13 @@ struct MyData {
     start: std::time::Instant,
 }
 
-pub fn read_line() -> String {
+// None once stdin runs out, so the loop can stop
+fn read_line() -> Option<String> {
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim().to_string()),
+    }
 }
 
 fn main() {
@@ -26,7 +27,9 @@ fn main() {
 
     loop {
         println!("Enter a string");
-        let _ = read_line();
+        if read_line().is_none() {
+            break;
+        }
         let data_to_move = MyData {
             start: std::time::Instant::now(),
         };
@@ -1,10 +1,10 @@
-fn read_line() -> String {
-    // <- Public function
+// None once stdin runs out, so the loop can stop
+fn read_line() -> Option<String> {
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim().to_string()),
+    }
 }
 
 fn parkable_thread(n: u32) {
@@ -23,14 +23,14 @@ fn main() {
 
     loop {
         println!("Enter a thread number to awaken, or q to quit");
-        let input = read_line();
+        let Some(input) = read_line() else { break };
         if input == "q" {
             break;
         }
-        if let Ok(number) = input.parse::<u32>() {
-            if number < threads.len() as u32 {
-                threads[number as usize].thread().unpark();
-            }
+        match input.parse::<usize>() {
+            Ok(n) if n < threads.len() => threads[n].thread().unpark(),
+            Ok(_) => println!("There are only {} threads", threads.len()),
+            Err(_) => println!("{input} isn't a thread number"),
         }
     }
 }
@@ -0,0 +1,51 @@
+use rayon::prelude::*;
+use std::time::Instant;
+
+const MAX: u64 = 2_000_000;
+
+// Deliberately slow, so there's real work to share out
+fn is_prime(n: u64) -> bool {
+    n >= 2 && (2..).take_while(|i| i * i <= n).all(|i| !n.is_multiple_of(i))
+}
+
+fn single_threaded() -> usize {
+    (0..MAX).filter(|n| is_prime(*n)).count()
+}
+
+fn scoped_threads() -> usize {
+    let threads = std::thread::available_parallelism().map_or(4, |n| n.get()) as u64;
+    let chunk_size = MAX.div_ceil(threads);
+
+    // One equal-sized chunk per thread. Big numbers take longer to check,
+    // so the last chunk finishes well after the first and its neighbours
+    // sit idle waiting for it.
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|i| {
+                let start = i * chunk_size;
+                let end = (start + chunk_size).min(MAX);
+                scope.spawn(move || (start..end).filter(|n| is_prime(*n)).count())
+            })
+            .collect();
+        handles.into_iter().map(|h| h.join().unwrap()).sum()
+    })
+}
+
+fn rayon() -> usize {
+    // Rayon splits the range into small pieces, and idle threads steal
+    // work from busy ones, so nobody waits on a slow chunk.
+    (0..MAX).into_par_iter().filter(|n| is_prime(*n)).count()
+}
+
+fn time(name: &str, f: fn() -> usize) {
+    let start = Instant::now();
+    let count = f();
+    println!("{name:<16} {count} primes in {:.2?}", start.elapsed());
+}
+
+fn main() {
+    println!("Counting primes below {MAX}, using {} threads", rayon::current_num_threads());
+    time("Single thread", single_threaded);
+    time("Scoped threads", scoped_threads);
+    time("Rayon", rayon);
+}
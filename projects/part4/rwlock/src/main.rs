@@ -7,13 +7,13 @@ fn build_users() -> Vec<String> {
     vec!["Alice".to_string(), "Bob".to_string()]
 }
 
-pub fn read_line() -> String {
-    // <- Public function
+// None once stdin runs out, so the loop can stop
+fn read_line() -> Option<String> {
     let mut input = String::new();
-    std::io::stdin()
-        .read_line(&mut input)
-        .expect("Failed to read line");
-    input.trim().to_string()
+    match std::io::stdin().read_line(&mut input) {
+        Ok(0) | Err(_) => None,
+        Ok(_) => Some(input.trim().to_string()),
+    }
 }
 
 fn main() {
@@ -28,10 +28,10 @@ fn main() {
 
     loop {
         println!("Enter a name to add to the list (or 'q' to quit):");
-        let input = read_line();
-        if input == "q" {
-            break;
-        }
+        let input = match read_line() {
+            Some(input) if input != "q" => input,
+            _ => break,
+        };
         let mut users = USERS.write().unwrap();
         users.push(input);
     }
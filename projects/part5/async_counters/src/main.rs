@@ -0,0 +1,105 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+const TASKS: u32 = 4;
+const PER_TASK: u32 = 250_000;
+
+// The async lock: waiting for it yields to the runtime instead of
+// blocking the thread. That's what you pay for in the timings.
+async fn tokio_mutex() -> u32 {
+    let counter = Arc::new(tokio::sync::Mutex::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..TASKS {
+        let counter = counter.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..PER_TASK {
+                *counter.lock().await += 1;
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    let count = *counter.lock().await;
+    count
+}
+
+// A std Mutex is fine in async code as long as the guard is never held
+// across an .await - here it's dropped at the end of each statement
+async fn std_mutex_in_task() -> u32 {
+    let counter = Arc::new(std::sync::Mutex::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..TASKS {
+        let counter = counter.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..PER_TASK {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    let count = *counter.lock().unwrap();
+    count
+}
+
+// Blocking work belongs on the blocking pool, where it can't stall the
+// tasks sharing a worker thread
+async fn std_mutex_spawn_blocking() -> u32 {
+    let counter = Arc::new(std::sync::Mutex::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..TASKS {
+        let counter = counter.clone();
+        handles.push(tokio::task::spawn_blocking(move || {
+            for _ in 0..PER_TASK {
+                *counter.lock().unwrap() += 1;
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    let count = *counter.lock().unwrap();
+    count
+}
+
+async fn atomic() -> u32 {
+    let counter = Arc::new(AtomicU32::new(0));
+    let mut handles = Vec::new();
+    for _ in 0..TASKS {
+        let counter = counter.clone();
+        handles.push(tokio::spawn(async move {
+            for _ in 0..PER_TASK {
+                counter.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+    }
+    for handle in handles {
+        handle.await.unwrap();
+    }
+    counter.load(Ordering::Relaxed)
+}
+
+async fn time<F: std::future::Future<Output = u32>>(name: &str, counter: F) -> Duration {
+    let start = Instant::now();
+    let count = counter.await;
+    let elapsed = start.elapsed();
+    println!("{name:<36} {count:>8} in {elapsed:.1?}");
+    elapsed
+}
+
+#[tokio::main]
+async fn main() {
+    println!("{TASKS} tasks adding 1 to a counter {PER_TASK} times each\n");
+    time("tokio::sync::Mutex", tokio_mutex()).await;
+    time("std::sync::Mutex in a task", std_mutex_in_task()).await;
+    time("std::sync::Mutex in spawn_blocking", std_mutex_spawn_blocking()).await;
+    time("AtomicU32", atomic()).await;
+
+    println!();
+    println!("Use an atomic if the shared state is a number. Use std::sync::Mutex for");
+    println!("anything else, unless you need to hold the lock across an .await - that's");
+    println!("the one job tokio::sync::Mutex is for.");
+}
@@ -0,0 +1,104 @@
+use std::time::Duration;
+use tokio::{sync::mpsc, time::sleep};
+use tokio_util::sync::CancellationToken;
+
+// Cancelling a future just means dropping it, so destructors are where
+// cleanup happens. This one tells us when that is.
+struct Cleanup(String);
+
+impl Drop for Cleanup {
+    fn drop(&mut self) {
+        println!("  {}: cleaned up", self.0);
+    }
+}
+
+async fn slow_operation(name: &'static str, seconds: u64) -> &'static str {
+    let _cleanup = Cleanup(name.to_string());
+    println!("  {name}: started");
+    sleep(Duration::from_secs(seconds)).await;
+    println!("  {name}: finished");
+    name
+}
+
+async fn race_a_timeout() {
+    println!("select! racing a slow operation against a timer:");
+    tokio::select! {
+        name = slow_operation("slow operation", 2) => println!("  {name} won"),
+        _ = sleep(Duration::from_secs(1)) => println!("  Timed out"),
+    }
+
+    // tokio::time::timeout wraps up the same pattern
+    println!("tokio::time::timeout:");
+    match tokio::time::timeout(Duration::from_secs(1), slow_operation("quick operation", 0)).await {
+        Ok(name) => println!("  {name} made it in time"),
+        Err(_) => println!("  Timed out"),
+    }
+}
+
+// Dropping a future can only interrupt it at an .await. A token lets the
+// task decide where it's safe to stop, and finish up on its own terms.
+async fn worker(id: u32, token: CancellationToken) {
+    let _cleanup = Cleanup(format!("worker {id}"));
+    let mut done = 0;
+    loop {
+        tokio::select! {
+            _ = token.cancelled() => {
+                println!("  worker {id}: cancelled after {done} jobs");
+                return;
+            }
+            _ = sleep(Duration::from_millis(300)) => done += 1,
+        }
+    }
+}
+
+async fn cancel_with_a_token() {
+    println!("CancellationToken:");
+    let token = CancellationToken::new();
+    // Cancelling the parent cancels its children too, but not the reverse
+    let workers = vec![
+        tokio::spawn(worker(1, token.child_token())),
+        tokio::spawn(worker(2, token.child_token())),
+    ];
+    sleep(Duration::from_secs(1)).await;
+    println!("  Cancelling");
+    token.cancel();
+    for worker in workers {
+        worker.await.unwrap();
+    }
+}
+
+// Only await cancel-safe futures in a select! loop: if another branch
+// wins, the one that lost must not have been halfway through something.
+// mpsc::Receiver::recv is safe - a message is either received or left in
+// the channel. Something like AsyncReadExt::read_exact isn't.
+async fn cancel_safe_loop() {
+    println!("Cancel-safe select! loop:");
+    let (tx, mut rx) = mpsc::channel(10);
+    tokio::spawn(async move {
+        for n in 0..5 {
+            let _ = tx.send(n).await;
+            sleep(Duration::from_millis(150)).await;
+        }
+    });
+
+    let mut tick = tokio::time::interval(Duration::from_millis(200));
+    loop {
+        tokio::select! {
+            message = rx.recv() => match message {
+                Some(n) => println!("  Received {n}"),
+                None => break,
+            },
+            // Each tick cancels the pending recv, and no message is lost
+            _ = tick.tick() => println!("  Tick"),
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    race_a_timeout().await;
+    println!();
+    cancel_with_a_token().await;
+    println!();
+    cancel_safe_loop().await;
+}
@@ -0,0 +1,48 @@
+use anyhow::bail;
+use framed_echo::{read_frame, write_frame, ADDRESS};
+use tokio::{io::AsyncWriteExt, net::TcpStream, task::JoinSet};
+
+const CONNECTIONS: usize = 10;
+const MESSAGES: usize = 5;
+
+async fn talk(id: usize) -> anyhow::Result<usize> {
+    let mut stream = TcpStream::connect(ADDRESS).await?;
+    for n in 0..MESSAGES {
+        let message = format!("Client {id}, message {n}");
+        write_frame(&mut stream, message.as_bytes()).await?;
+        match read_frame(&mut stream).await? {
+            Some(reply) if reply == message.as_bytes() => {}
+            Some(_) => bail!("Client {id}: echo didn't match"),
+            None => bail!("Client {id}: server hung up"),
+        }
+    }
+
+    // Close our half, then wait for the server to close its half
+    stream.shutdown().await?;
+    if read_frame(&mut stream).await?.is_some() {
+        bail!("Client {id}: unexpected frame after shutdown");
+    }
+    Ok(MESSAGES)
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let mut clients = JoinSet::new();
+    for id in 0..CONNECTIONS {
+        clients.spawn(talk(id));
+    }
+
+    let mut echoed = 0;
+    let mut failed = 0;
+    while let Some(result) = clients.join_next().await {
+        match result? {
+            Ok(n) => echoed += n,
+            Err(e) => {
+                println!("{e}");
+                failed += 1;
+            }
+        }
+    }
+    println!("{CONNECTIONS} connections: {echoed} messages echoed, {failed} failed");
+    Ok(())
+}
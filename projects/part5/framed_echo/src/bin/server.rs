@@ -0,0 +1,75 @@
+use framed_echo::{read_frame, write_frame, ADDRESS};
+use std::{net::SocketAddr, time::Duration};
+use tokio::{
+    io::AsyncWriteExt,
+    net::{TcpListener, TcpStream},
+    sync::watch,
+    task::JoinSet,
+};
+
+async fn echo(mut socket: TcpStream, address: SocketAddr, mut shutdown: watch::Receiver<bool>) {
+    println!("{address}: connected");
+    loop {
+        // read_frame isn't cancel safe - a half-read frame is lost if
+        // shutdown wins - but we're hanging up anyway, so that's fine
+        let frame = tokio::select! {
+            frame = read_frame(&mut socket) => frame,
+            _ = shutdown.changed() => {
+                println!("{address}: server shutting down");
+                break;
+            }
+        };
+        match frame {
+            Ok(Some(payload)) => {
+                if let Err(e) = write_frame(&mut socket, &payload).await {
+                    println!("{address}: {e}");
+                    return;
+                }
+            }
+            Ok(None) => {
+                println!("{address}: closed by client");
+                break;
+            }
+            Err(e) => {
+                println!("{address}: {e}");
+                return;
+            }
+        }
+    }
+    // Tell the client we're done sending
+    let _ = socket.shutdown().await;
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let listener = TcpListener::bind(ADDRESS).await?;
+    println!("Listening on {ADDRESS}, Ctrl+C to stop");
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    // Every connection task lives in the set, so none outlive main
+    let mut connections = JoinSet::new();
+    loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let (socket, address) = accepted?;
+                connections.spawn(echo(socket, address, shutdown_rx.clone()));
+            }
+            // Reap finished connections as we go, so the set doesn't grow
+            Some(_) = connections.join_next() => {}
+            _ = tokio::signal::ctrl_c() => break,
+        }
+    }
+
+    println!("Stopping: closing {} connections", connections.len());
+    drop(listener);
+    let _ = shutdown_tx.send(true);
+    let drained = tokio::time::timeout(Duration::from_secs(5), async {
+        while connections.join_next().await.is_some() {}
+    })
+    .await;
+    if drained.is_err() {
+        // Dropping the set aborts whatever's left
+        println!("Gave up waiting for {} connections", connections.len());
+    }
+    Ok(())
+}
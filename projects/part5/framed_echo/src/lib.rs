@@ -0,0 +1,72 @@
+//! Frames for the echo server and client: a big-endian `u32` length
+//! followed by that many bytes. TCP is a stream, so without a length there's
+//! no telling where one message ends and the next begins.
+
+use anyhow::{bail, Result};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt};
+
+pub const ADDRESS: &str = "127.0.0.1:8124";
+
+/// Refuse anything bigger, rather than allocate whatever the peer asks for
+pub const MAX_FRAME: usize = 64 * 1024;
+
+pub async fn write_frame<W: AsyncWrite + Unpin>(writer: &mut W, payload: &[u8]) -> Result<()> {
+    if payload.len() > MAX_FRAME {
+        bail!("Frame of {} bytes is over the {MAX_FRAME} byte limit", payload.len());
+    }
+    writer.write_u32(payload.len() as u32).await?;
+    writer.write_all(payload).await?;
+    writer.flush().await?;
+    Ok(())
+}
+
+/// Reads one frame. Returns `None` if the peer closed the connection
+/// cleanly between frames.
+pub async fn read_frame<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Option<Vec<u8>>> {
+    let len = match reader.read_u32().await {
+        Ok(len) => len as usize,
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    if len > MAX_FRAME {
+        bail!("Frame of {len} bytes is over the {MAX_FRAME} byte limit");
+    }
+    let mut payload = vec![0; len];
+    reader.read_exact(&mut payload).await?;
+    Ok(Some(payload))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn frames_round_trip() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        write_frame(&mut client, b"Hello").await.unwrap();
+        write_frame(&mut client, b"").await.unwrap();
+        drop(client);
+
+        assert_eq!(read_frame(&mut server).await.unwrap(), Some(b"Hello".to_vec()));
+        assert_eq!(read_frame(&mut server).await.unwrap(), Some(Vec::new()));
+        assert_eq!(read_frame(&mut server).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn truncated_frame_is_an_error() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        client.write_u32(10).await.unwrap();
+        client.write_all(b"short").await.unwrap();
+        drop(client);
+
+        assert!(read_frame(&mut server).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn oversized_frame_is_refused() {
+        let (mut client, mut server) = tokio::io::duplex(1024);
+        client.write_u32(MAX_FRAME as u32 + 1).await.unwrap();
+
+        assert!(read_frame(&mut server).await.is_err());
+    }
+}
@@ -0,0 +1,111 @@
+use std::process::Command;
+use std::time::{Duration, Instant};
+
+const COUNTS: [usize; 4] = [100, 1_000, 10_000, 100_000];
+const SLEEP: Duration = Duration::from_millis(10);
+
+// A little bit of work after the sleep, so the optimizer can't skip it
+fn compute(n: usize) -> u64 {
+    (0..1000u64).fold(n as u64, |acc, x| acc.wrapping_mul(31).wrapping_add(x))
+}
+
+fn run_threads(n: usize) -> Result<u64, String> {
+    let mut handles = Vec::with_capacity(n);
+    for i in 0..n {
+        let handle = std::thread::Builder::new()
+            .spawn(move || {
+                std::thread::sleep(SLEEP);
+                compute(i)
+            })
+            .map_err(|e| format!("failed after {i} threads: {e}"))?;
+        handles.push(handle);
+    }
+    Ok(handles.into_iter().map(|h| h.join().unwrap()).fold(0, u64::wrapping_add))
+}
+
+fn run_tasks(n: usize) -> Result<u64, String> {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    runtime.block_on(async {
+        let handles: Vec<_> = (0..n)
+            .map(|i| {
+                tokio::spawn(async move {
+                    tokio::time::sleep(SLEEP).await;
+                    compute(i)
+                })
+            })
+            .collect();
+        let mut total = 0u64;
+        for handle in handles {
+            total = total.wrapping_add(handle.await.unwrap());
+        }
+        Ok(total)
+    })
+}
+
+// The most memory the process has had resident at once (Linux only)
+fn peak_memory_kb() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmHWM:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+// Runs one measurement and prints "<ms> <peak kB>" for the parent to read
+fn child(kind: &str, n: usize) {
+    let start = Instant::now();
+    let result = match kind {
+        "threads" => run_threads(n),
+        _ => run_tasks(n),
+    };
+    match result {
+        Ok(_) => println!(
+            "{} {}",
+            start.elapsed().as_millis(),
+            peak_memory_kb().map_or("?".to_string(), |kb| kb.to_string())
+        ),
+        Err(e) => println!("error {e}"),
+    }
+}
+
+fn measure(kind: &str, n: usize) -> String {
+    // A fresh process for every run, so each gets its own peak memory
+    let output = Command::new(std::env::current_exe().unwrap())
+        .args([kind, &n.to_string()])
+        .output()
+        .expect("Unable to run the measurement");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match stdout.trim().split_once(' ') {
+        Some(("error", e)) => e.to_string(),
+        Some((ms, kb)) => format!("{ms} ms, {kb} kB"),
+        // A thread can also fail after it's spawned, while setting up its
+        // stack, and then all Rust can do is abort
+        None => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            match stderr.lines().find(|line| line.contains("os error")) {
+                Some(line) => format!("crashed: {}", line.rsplit(": ").next().unwrap()),
+                None => format!("crashed ({})", output.status),
+            }
+        }
+    }
+}
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if let [_, kind, n] = args.as_slice() {
+        child(kind, n.parse().expect("Count must be a number"));
+        return;
+    }
+
+    println!("Each thread or task sleeps for {SLEEP:?} and then does a little maths\n");
+    println!("| {:>7} | {:<32} | {:<32} |", "count", "OS threads", "tokio tasks");
+    println!("|{:-<9}|{:-<34}|{:-<34}|", "", "", "");
+    for n in COUNTS {
+        println!(
+            "| {n:>7} | {:<32} | {:<32} |",
+            measure("threads", n),
+            measure("tasks", n)
+        );
+    }
+    println!();
+    println!("How many OS threads you can have at once depends on `ulimit -u`,");
+    println!("/proc/sys/kernel/threads-max and /proc/sys/vm/max_map_count.");
+}
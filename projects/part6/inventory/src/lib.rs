@@ -0,0 +1,232 @@
+/// Anything that can go in an inventory. Implementors only have to provide
+/// the first three methods; the rest have default implementations they can
+/// use or override.
+pub trait Item {
+    fn name(&self) -> &str;
+    fn weight(&self) -> f32;
+    fn value(&self) -> u32;
+
+    fn describe(&self) -> String {
+        format!("{} ({:.1}kg, {} gold)", self.name(), self.weight(), self.value())
+    }
+
+    fn value_per_kg(&self) -> f32 {
+        self.value() as f32 / self.weight()
+    }
+}
+
+pub struct Sword {
+    pub damage: u32,
+}
+
+impl Item for Sword {
+    fn name(&self) -> &str {
+        "Sword"
+    }
+
+    fn weight(&self) -> f32 {
+        3.0
+    }
+
+    fn value(&self) -> u32 {
+        self.damage * 10
+    }
+
+    // Overrides the default
+    fn describe(&self) -> String {
+        format!("Sword doing {} damage ({} gold)", self.damage, self.value())
+    }
+}
+
+pub struct Potion {
+    pub name: String,
+    pub value: u32,
+}
+
+impl Item for Potion {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn weight(&self) -> f32 {
+        0.5
+    }
+
+    fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+/// A blanket impl: every `Box` of an `Item` is an `Item` too. `?Sized`
+/// includes `Box<dyn Item>`, which is what lets one inventory hold swords
+/// and potions together.
+impl<I: Item + ?Sized> Item for Box<I> {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+
+    fn weight(&self) -> f32 {
+        (**self).weight()
+    }
+
+    fn value(&self) -> u32 {
+        (**self).value()
+    }
+
+    fn describe(&self) -> String {
+        (**self).describe()
+    }
+}
+
+pub trait Summary {
+    fn summary(&self) -> String;
+}
+
+/// Another blanket impl: implement `Item`, and you get `Summary` for free.
+impl<T: Item> Summary for T {
+    fn summary(&self) -> String {
+        format!("{}: {:.1} gold/kg", self.name(), self.value_per_kg())
+    }
+}
+
+/// Holds items of one type `T`, up to a weight limit. The compiler makes a
+/// separate copy of this code for every `T` it's used with
+/// (monomorphization), so there's no dynamic dispatch unless `T` is itself
+/// a trait object.
+pub struct Inventory<T: Item> {
+    items: Vec<T>,
+    max_weight: f32,
+}
+
+impl<T: Item> Inventory<T> {
+    pub fn new(max_weight: f32) -> Self {
+        Self {
+            items: Vec::new(),
+            max_weight,
+        }
+    }
+
+    /// Hands the item back if it would take us over the weight limit.
+    pub fn add(&mut self, item: T) -> Result<(), T> {
+        if self.total_weight() + item.weight() > self.max_weight {
+            return Err(item);
+        }
+        self.items.push(item);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<T> {
+        let index = self.items.iter().position(|item| item.name() == name)?;
+        Some(self.items.remove(index))
+    }
+
+    pub fn total_weight(&self) -> f32 {
+        self.items.iter().map(Item::weight).sum()
+    }
+
+    pub fn total_value(&self) -> u32 {
+        self.items.iter().map(Item::value).sum()
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// `impl Trait` in return position: the caller gets "some iterator" and
+    /// doesn't need to spell out the Filter<Iter<..>, closure> type
+    pub fn worth_at_least(&self, min_value: u32) -> impl Iterator<Item = &T> + '_ {
+        self.items.iter().filter(move |item| item.value() >= min_value)
+    }
+}
+
+/// Generic: a separate, fully inlinable copy for each `T`.
+pub fn total_value<T: Item>(items: &[T]) -> u32 {
+    items.iter().map(Item::value).sum()
+}
+
+/// Trait objects: one copy, calling `value` through the vtable.
+pub fn total_value_dyn(items: &[&dyn Item]) -> u32 {
+    items.iter().map(|item| item.value()).sum()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn potion(name: &str, value: u32) -> Potion {
+        Potion {
+            name: name.to_string(),
+            value,
+        }
+    }
+
+    #[test]
+    fn default_and_overridden_methods() {
+        assert_eq!(potion("Healing", 50).describe(), "Healing (0.5kg, 50 gold)");
+        assert_eq!(Sword { damage: 7 }.describe(), "Sword doing 7 damage (70 gold)");
+        assert_eq!(potion("Healing", 50).value_per_kg(), 100.0);
+    }
+
+    #[test]
+    fn blanket_summary() {
+        assert_eq!(potion("Healing", 50).summary(), "Healing: 100.0 gold/kg");
+        // Box<Sword> is an Item through the Box impl, so it gets Summary too
+        assert_eq!(Box::new(Sword { damage: 3 }).summary(), "Sword: 10.0 gold/kg");
+    }
+
+    #[test]
+    fn weight_limit() {
+        let mut bag = Inventory::new(1.0);
+        assert!(bag.add(potion("Healing", 50)).is_ok());
+        assert!(bag.add(potion("Mana", 40)).is_ok());
+        let rejected = bag.add(potion("Speed", 30)).unwrap_err();
+        assert_eq!(rejected.name, "Speed");
+        assert_eq!(bag.len(), 2);
+        assert_eq!(bag.total_value(), 90);
+    }
+
+    #[test]
+    fn remove_and_filter() {
+        let mut bag = Inventory::new(10.0);
+        for (name, value) in [("Healing", 50), ("Mana", 40), ("Speed", 10)] {
+            assert!(bag.add(potion(name, value)).is_ok());
+        }
+        let valuable: Vec<&str> = bag.worth_at_least(40).map(|p| p.name()).collect();
+        assert_eq!(valuable, ["Healing", "Mana"]);
+        assert_eq!(bag.remove("Mana").unwrap().value, 40);
+        assert!(bag.remove("Mana").is_none());
+    }
+
+    // Inventory<Potion> can only ever hold potions - that's checked at
+    // compile time - and total_value::<Potion> calls Potion::value directly
+    #[test]
+    fn monomorphized_functions_take_one_type() {
+        let potions = [potion("Healing", 50), potion("Mana", 40)];
+        assert_eq!(total_value(&potions), 90);
+    }
+
+    // Inventory<Box<dyn Item>> can mix types. Each entry is a "fat"
+    // pointer: the data pointer plus a pointer to the type's vtable
+    #[test]
+    fn trait_object_inventory_mixes_types() {
+        assert_eq!(
+            std::mem::size_of::<Box<dyn Item>>(),
+            2 * std::mem::size_of::<Box<Potion>>()
+        );
+
+        let mut bag: Inventory<Box<dyn Item>> = Inventory::new(10.0);
+        assert!(bag.add(Box::new(Sword { damage: 5 })).is_ok());
+        assert!(bag.add(Box::new(potion("Healing", 50))).is_ok());
+        assert_eq!(bag.total_value(), 100);
+        assert_eq!(bag.total_weight(), 3.5);
+
+        let sword = Sword { damage: 5 };
+        let healing = potion("Healing", 50);
+        let items: [&dyn Item; 2] = [&sword, &healing];
+        assert_eq!(total_value_dyn(&items), 100);
+    }
+}
@@ -0,0 +1,31 @@
+use inventory::{Inventory, Item, Potion, Summary, Sword};
+
+fn main() {
+    // One type of item: everything is resolved at compile time
+    let mut potions = Inventory::new(1.0);
+    for (name, value) in [("Healing", 50), ("Mana", 40), ("Speed", 30)] {
+        let potion = Potion { name: name.to_string(), value };
+        if let Err(potion) = potions.add(potion) {
+            println!("No room for {}", potion.describe());
+        }
+    }
+    println!("Potions worth 40+ gold:");
+    for potion in potions.worth_at_least(40) {
+        println!("  {}", potion.summary());
+    }
+
+    // Mixed items, through trait objects
+    let mut backpack: Inventory<Box<dyn Item>> = Inventory::new(10.0);
+    backpack.add(Box::new(Sword { damage: 8 })).ok();
+    backpack.add(Box::new(Potion { name: "Healing".to_string(), value: 50 })).ok();
+    println!("Backpack:");
+    for item in backpack.worth_at_least(0) {
+        println!("  {}", item.describe());
+    }
+    println!(
+        "  {} items, {:.1}kg, {} gold",
+        backpack.len(),
+        backpack.total_weight(),
+        backpack.total_value()
+    );
+}
@@ -1,4 +1,4 @@
-use sqlx::{Row, FromRow};
+use sqlx::FromRow;
 
 #[derive(Debug, FromRow)]
 struct Message {
@@ -18,7 +18,7 @@ async fn update_message(id: i64, message: &str, pool: &sqlx::SqlitePool) -> anyh
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     // Enable tracing
-    tracing_subscriber::fmt::init();
+    telemetry::init("info");
 
     // Read the .env file and obtain the database URL
     dotenv::dotenv()?;
@@ -54,7 +54,7 @@ async fn main() -> anyhow::Result<()> {
 
     // Print the messages
     for message in messages.into_iter() {
-        println!("{message:?}");
+        println!("{}: {}", message.id, message.message);
     }
 
     // Or as a stream
@@ -63,7 +63,7 @@ async fn main() -> anyhow::Result<()> {
     let mut message_stream = sqlx::query_as::<_, Message>("SELECT id, message FROM messages")
         .fetch(&pool);
     while let Some(message) = message_stream.try_next().await? {
-        println!("{message:?}");
+        println!("{}: {}", message.id, message.message);
     }
 
 
@@ -5,11 +5,16 @@ use axum::response::Html;
 
 #[tokio::main]
 async fn main() {
+    telemetry::init("info");
     let app = Router::new()
         .route("/", get(say_hello_file))
+        .route("/text", get(say_hello_text))
+        .route("/html", get(say_hello_html))
+        .route("/included", get(say_hello_html_included))
         .route("/json", get(say_hello_json))
         .route("/post", post(say_hello_post));
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));    
+    tracing::info!("Listening on http://{addr}");
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
         .await
@@ -0,0 +1,533 @@
+use auth_core::{
+    change_password_with_store, login_with_store, register_with_store, JsonFileStore, LoginAction, LoginRole,
+    PasswordChange, RateLimited, RateLimiter, RegistrationError,
+};
+use axum::{
+    extract::ConnectInfo,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex, RwLock},
+};
+
+struct Session {
+    // Password logins use the lowercase name from users.json, so "Bob" and
+    // "bob" are the same user
+    username: String,
+    role: LoginRole,
+}
+
+// Session token -> who it belongs to
+type Sessions = Arc<RwLock<HashMap<String, Session>>>;
+
+type Limiter = Arc<Mutex<RateLimiter>>;
+
+// users.json is read, changed and written back whole, so only one request
+// at a time gets to use it
+type Store = Arc<tokio::sync::Mutex<JsonFileStore>>;
+
+#[cfg(feature = "oauth")]
+mod oauth;
+
+#[tokio::main]
+async fn main() {
+    telemetry::init("info");
+    let store: Store = Arc::new(tokio::sync::Mutex::new(JsonFileStore::default()));
+    let sessions: Sessions = Arc::new(RwLock::new(HashMap::new()));
+    let limiter: Limiter = Arc::new(Mutex::new(RateLimiter::default()));
+
+    // Forget about quiet usernames and addresses now and then
+    let pruned = limiter.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(std::time::Duration::from_secs(60));
+        loop {
+            interval.tick().await;
+            pruned.lock().unwrap().prune();
+        }
+    });
+
+    let app = routes();
+
+    #[cfg(feature = "oauth")]
+    let app = match oauth::OAuth::from_env().await {
+        Ok(Some(oauth)) => app
+            .route("/oauth/login", get(oauth::login))
+            .route("/oauth/callback", get(oauth::callback))
+            .layer(Extension(Arc::new(oauth))),
+        Ok(None) => {
+            tracing::info!("OIDC_ISSUER_URL is not set, so /oauth/login is disabled");
+            app
+        }
+        Err(e) => {
+            tracing::error!("Unable to set up OpenID Connect, so /oauth/login is disabled: {e}");
+            app
+        }
+    };
+
+    let app = app
+        .layer(Extension(store))
+        .layer(Extension(sessions))
+        .layer(Extension(limiter));
+    let addr = SocketAddr::from(([127, 0, 0, 1], 3002));
+    tracing::info!("Listening on http://{addr}");
+    axum::Server::bind(&addr)
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .await
+        .unwrap();
+}
+
+fn routes() -> Router {
+    Router::new()
+        .route("/login", post(login))
+        .route("/logout", post(logout))
+        .route("/whoami", get(whoami))
+        .route("/password", post(password))
+        .route("/register", post(register_user))
+}
+
+struct ApiError {
+    status: StatusCode,
+    message: String,
+    retry_after: Option<u64>,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: &str) -> Self {
+        Self {
+            status,
+            message: message.to_string(),
+            retry_after: None,
+        }
+    }
+}
+
+impl From<RateLimited> for ApiError {
+    fn from(limited: RateLimited) -> Self {
+        Self {
+            status: StatusCode::TOO_MANY_REQUESTS,
+            message: limited.to_string(),
+            retry_after: Some(limited.retry_after_secs()),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+    error: String,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let mut response = (self.status, Json(ErrorJson { error: self.message })).into_response();
+        if let Some(seconds) = self.retry_after {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, seconds.into());
+        }
+        response
+    }
+}
+
+#[derive(Deserialize)]
+struct Credentials {
+    username: String,
+    password: String,
+}
+
+#[derive(Deserialize)]
+struct NewPassword {
+    old_password: String,
+    new_password: String,
+}
+
+#[derive(Serialize)]
+struct LoginResponse {
+    token: String,
+    role: LoginRole,
+}
+
+#[derive(Serialize)]
+struct Registered {
+    username: String,
+    role: LoginRole,
+}
+
+#[derive(Serialize)]
+struct WhoAmI {
+    username: String,
+    role: LoginRole,
+}
+
+fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    headers
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Bearer ")
+}
+
+async fn login(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(store): Extension<Store>,
+    Extension(sessions): Extension<Sessions>,
+    Extension(limiter): Extension<Limiter>,
+    Json(credentials): Json<Credentials>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    limiter
+        .lock()
+        .unwrap()
+        .check(&credentials.username, &addr.ip().to_string())?;
+
+    // The login library reads files, so keep it off the async worker threads
+    let username = credentials.username.to_lowercase();
+    let source = addr.ip().to_string();
+    // A login may read the file halfway through a registration writing it
+    let store = store.lock_owned().await;
+    let result = tokio::task::spawn_blocking(move || {
+        login_with_store(&*store, &credentials.username, &credentials.password, &source)
+    })
+    .await
+    .map_err(|_| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Login task failed"))?;
+
+    match result {
+        Ok(LoginAction::Granted(role)) => {
+            let token = uuid::Uuid::new_v4().to_string();
+            sessions.write().unwrap().insert(
+                token.clone(),
+                Session {
+                    username,
+                    role: role.clone(),
+                },
+            );
+            Ok(Json(LoginResponse { token, role }))
+        }
+        Ok(LoginAction::Denied) => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Invalid username or password",
+        )),
+        Err(e) => {
+            tracing::error!("Login error: {e}");
+            Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Unable to check credentials",
+            ))
+        }
+    }
+}
+
+async fn logout(
+    Extension(sessions): Extension<Sessions>,
+    headers: HeaderMap,
+) -> Result<StatusCode, ApiError> {
+    let token = bearer_token(&headers)
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Missing bearer token"))?;
+    if sessions.write().unwrap().remove(token).is_some() {
+        Ok(StatusCode::NO_CONTENT)
+    } else {
+        Err(ApiError::new(StatusCode::UNAUTHORIZED, "Unknown session"))
+    }
+}
+
+async fn whoami(
+    Extension(sessions): Extension<Sessions>,
+    headers: HeaderMap,
+) -> Result<Json<WhoAmI>, ApiError> {
+    let token = bearer_token(&headers)
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Missing bearer token"))?;
+    let sessions = sessions.read().unwrap();
+    let session = sessions
+        .get(token)
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Unknown session"))?;
+    Ok(Json(WhoAmI {
+        username: session.username.clone(),
+        role: session.role.clone(),
+    }))
+}
+
+async fn password(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(store): Extension<Store>,
+    Extension(sessions): Extension<Sessions>,
+    Extension(limiter): Extension<Limiter>,
+    headers: HeaderMap,
+    Json(change): Json<NewPassword>,
+) -> Result<StatusCode, ApiError> {
+    let token = bearer_token(&headers)
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Missing bearer token"))?
+        .to_string();
+    let username = sessions
+        .read()
+        .unwrap()
+        .get(&token)
+        .map(|session| session.username.clone())
+        .ok_or_else(|| ApiError::new(StatusCode::UNAUTHORIZED, "Unknown session"))?;
+
+    // Guessing the old password is just another way to guess a password
+    limiter
+        .lock()
+        .unwrap()
+        .check(&username, &addr.ip().to_string())?;
+
+    let user = username.clone();
+    let store = store.lock_owned().await;
+    let result = tokio::task::spawn_blocking(move || {
+        change_password_with_store(&*store, &user, &change.old_password, &change.new_password)
+    })
+    .await
+    .map_err(|_| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Password task failed"))?;
+
+    match result {
+        Ok(PasswordChange::Changed) => {
+            // Anyone else logged in with the old password gets signed out
+            sessions
+                .write()
+                .unwrap()
+                .retain(|t, session| *t == token || session.username != username);
+            Ok(StatusCode::NO_CONTENT)
+        }
+        Ok(PasswordChange::Denied) => Err(ApiError::new(
+            StatusCode::UNAUTHORIZED,
+            "Invalid old password",
+        )),
+        Ok(PasswordChange::Rejected(violations)) => {
+            let reasons: Vec<String> = violations.iter().map(|v| format!("password {v}")).collect();
+            Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                &format!("New password rejected: {}", reasons.join(", ")),
+            ))
+        }
+        Err(e) => {
+            tracing::error!("Password change error: {e}");
+            Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Unable to change password",
+            ))
+        }
+    }
+}
+
+async fn register_user(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Extension(store): Extension<Store>,
+    Extension(limiter): Extension<Limiter>,
+    Json(credentials): Json<Credentials>,
+) -> Result<(StatusCode, Json<Registered>), ApiError> {
+    // Stops one address from creating (or probing for) lots of accounts
+    limiter
+        .lock()
+        .unwrap()
+        .check(&credentials.username, &addr.ip().to_string())?;
+
+    let store = store.lock_owned().await;
+    let result = tokio::task::spawn_blocking(move || {
+        register_with_store(&*store, &credentials.username, &credentials.password)
+    })
+    .await
+    .map_err(|_| ApiError::new(StatusCode::INTERNAL_SERVER_ERROR, "Registration task failed"))?;
+
+    match result {
+        Ok(user) => Ok((
+            StatusCode::CREATED,
+            Json(Registered {
+                username: user.username,
+                role: user.role,
+            }),
+        )),
+        Err(RegistrationError::EmptyUsername) => Err(ApiError::new(
+            StatusCode::BAD_REQUEST,
+            "The username can't be empty",
+        )),
+        Err(RegistrationError::UsernameTaken(_)) => Err(ApiError::new(
+            StatusCode::CONFLICT,
+            "That username is already taken",
+        )),
+        Err(RegistrationError::WeakPassword(violations)) => {
+            let reasons: Vec<String> = violations.iter().map(|v| format!("password {v}")).collect();
+            Err(ApiError::new(
+                StatusCode::UNPROCESSABLE_ENTITY,
+                &format!("Password rejected: {}", reasons.join(", ")),
+            ))
+        }
+        Err(RegistrationError::Login(e)) => {
+            tracing::error!("Registration error: {e}");
+            Err(ApiError::new(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Unable to create the account",
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{
+        body::Body,
+        extract::connect_info::MockConnectInfo,
+        http::{Method, Request},
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    // Logins made by the tests are audited in the temp directory
+    fn use_temp_audit_log() {
+        static ONCE: std::sync::Once = std::sync::Once::new();
+        ONCE.call_once(|| {
+            let name = format!("login_web_audit_{}.jsonl", std::process::id());
+            std::env::set_var("AUDIT_LOG", std::env::temp_dir().join(name));
+        });
+    }
+
+    // A router with its own users file, seeded with admin and bob
+    fn test_app(name: &str) -> Router {
+        use_temp_audit_log();
+        let path = std::env::temp_dir().join(format!("login_web_{name}_{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let store: Store = Arc::new(tokio::sync::Mutex::new(JsonFileStore::new(path)));
+        let sessions: Sessions = Arc::new(RwLock::new(HashMap::new()));
+        let limiter: Limiter = Arc::new(Mutex::new(RateLimiter::default()));
+        routes()
+            .layer(Extension(store))
+            .layer(Extension(sessions))
+            .layer(Extension(limiter))
+            .layer(MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 3002))))
+    }
+
+    async fn call(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Value,
+    ) -> Response {
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json");
+        if let Some(token) = token {
+            request = request.header(header::AUTHORIZATION, format!("Bearer {token}"));
+        }
+        let request = request.body(Body::from(body.to_string())).unwrap();
+        app.clone().oneshot(request).await.unwrap()
+    }
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        token: Option<&str>,
+        body: Value,
+    ) -> (StatusCode, Value) {
+        let response = call(app, method, uri, token, body).await;
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let json = serde_json::from_slice(&bytes).unwrap_or(Value::Null);
+        (status, json)
+    }
+
+    async fn post(app: &Router, uri: &str, body: Value) -> (StatusCode, Value) {
+        send(app, Method::POST, uri, None, body).await
+    }
+
+    #[tokio::test]
+    async fn good_login_starts_a_session() {
+        let app = test_app("good_login");
+        let (status, body) = post(&app, "/login", json!({"username": "admin", "password": "password"})).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["role"], "Admin");
+        let token = body["token"].as_str().unwrap();
+
+        let (status, whoami) = send(&app, Method::GET, "/whoami", Some(token), Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(whoami["username"], "admin");
+        assert_eq!(whoami["role"], "Admin");
+    }
+
+    #[tokio::test]
+    async fn bad_password_and_unknown_user_look_the_same() {
+        let app = test_app("bad_login");
+        let bad_password = post(&app, "/login", json!({"username": "bob", "password": "wrong"})).await;
+        let unknown_user = post(&app, "/login", json!({"username": "nobody", "password": "password"})).await;
+        assert_eq!(bad_password.0, StatusCode::UNAUTHORIZED);
+        assert_eq!(bad_password.1["error"], "Invalid username or password");
+        assert_eq!(bad_password, unknown_user);
+    }
+
+    #[tokio::test]
+    async fn too_many_attempts_are_rate_limited() {
+        let app = test_app("rate_limit");
+        for _ in 0..3 {
+            let (status, _) = post(&app, "/login", json!({"username": "bob", "password": "wrong"})).await;
+            assert_eq!(status, StatusCode::UNAUTHORIZED);
+        }
+
+        // Even the right password has to wait
+        let credentials = json!({"username": "bob", "password": "password"});
+        let response = call(&app, Method::POST, "/login", None, credentials).await;
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(retry_after > 0);
+    }
+
+    #[tokio::test]
+    async fn logout_ends_the_session() {
+        let app = test_app("logout");
+        let (_, body) = post(&app, "/login", json!({"username": "bob", "password": "password"})).await;
+        let token = body["token"].as_str().unwrap();
+
+        let (status, _) = send(&app, Method::POST, "/logout", Some(token), Value::Null).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&app, Method::GET, "/whoami", Some(token), Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(&app, Method::POST, "/logout", Some(token), Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        let (status, _) = send(&app, Method::POST, "/logout", None, Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn concurrent_registrations_are_all_saved() {
+        let app = test_app("concurrent");
+        let (alice, carol) = tokio::join!(
+            post(&app, "/register", json!({"username": "alice", "password": "Str0ng!Pass"})),
+            post(&app, "/register", json!({"username": "carol", "password": "Str0ng!Pass"})),
+        );
+        assert_eq!(alice.0, StatusCode::CREATED);
+        assert_eq!(carol.0, StatusCode::CREATED);
+
+        for username in ["alice", "carol"] {
+            let credentials = json!({"username": username, "password": "Str0ng!Pass"});
+            let (status, _) = post(&app, "/login", credentials).await;
+            assert_eq!(status, StatusCode::OK, "{username} should have been saved");
+        }
+    }
+
+    #[tokio::test]
+    async fn changing_password_signs_out_other_sessions_whatever_the_case() {
+        let app = test_app("password");
+        let (_, first) = post(&app, "/login", json!({"username": "Bob", "password": "password"})).await;
+        let (_, second) = post(&app, "/login", json!({"username": "BOB", "password": "password"})).await;
+        let first = first["token"].as_str().unwrap();
+        let second = second["token"].as_str().unwrap();
+
+        let change = json!({"old_password": "password", "new_password": "N3wPassw0rd"});
+        let (status, _) = send(&app, Method::POST, "/password", Some(first), change).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, whoami) = send(&app, Method::GET, "/whoami", Some(first), Value::Null).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(whoami["username"], "bob");
+        let (status, _) = send(&app, Method::GET, "/whoami", Some(second), Value::Null).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
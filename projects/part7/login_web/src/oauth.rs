@@ -0,0 +1,196 @@
+use crate::{ApiError, LoginResponse, Session, Sessions};
+use auth_core::LoginRole;
+use axum::{extract::Query, http::StatusCode, response::Redirect, Extension, Json};
+use openidconnect::{
+    core::{CoreAuthenticationFlow, CoreClient, CoreProviderMetadata},
+    reqwest::async_http_client,
+    AccessTokenHash, AuthorizationCode, ClientId, ClientSecret, CsrfToken, IssuerUrl, Nonce,
+    OAuth2TokenResponse, PkceCodeChallenge, PkceCodeVerifier, RedirectUrl, Scope, TokenResponse,
+};
+use serde::Deserialize;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+// How long the user has to finish logging in at the provider
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+
+// What we need to remember between sending the user to the provider and
+// them coming back to /oauth/callback
+struct PendingLogin {
+    pkce_verifier: PkceCodeVerifier,
+    nonce: Nonce,
+    started: Instant,
+}
+
+pub struct OAuth {
+    client: CoreClient,
+    /// Verified emails (compared ignoring case) or exact subject ids that
+    /// get `LoginRole::Admin`
+    admins: Vec<String>,
+    // Keyed by the CSRF state we sent, which the provider hands back
+    pending: Mutex<HashMap<String, PendingLogin>>,
+}
+
+pub type SharedOAuth = Arc<OAuth>;
+
+fn env(name: &str) -> Result<String, String> {
+    std::env::var(name).map_err(|_| format!("{name} is not set"))
+}
+
+impl OAuth {
+    /// Configures the provider from `OIDC_ISSUER_URL`, `OIDC_CLIENT_ID`,
+    /// `OIDC_CLIENT_SECRET`, `OIDC_REDIRECT_URL` and `OIDC_ADMINS` (a comma
+    /// separated list). Returns `None` if `OIDC_ISSUER_URL` isn't set.
+    pub async fn from_env() -> Result<Option<Self>, String> {
+        let Ok(issuer) = std::env::var("OIDC_ISSUER_URL") else {
+            return Ok(None);
+        };
+        let issuer = IssuerUrl::new(issuer).map_err(|e| e.to_string())?;
+        let client_id = ClientId::new(env("OIDC_CLIENT_ID")?);
+        let client_secret = std::env::var("OIDC_CLIENT_SECRET").ok().map(ClientSecret::new);
+        let redirect_url = std::env::var("OIDC_REDIRECT_URL")
+            .unwrap_or_else(|_| "http://localhost:3002/oauth/callback".to_string());
+        let redirect_url = RedirectUrl::new(redirect_url).map_err(|e| e.to_string())?;
+        let admins = std::env::var("OIDC_ADMINS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|admin| admin.trim().to_string())
+            .filter(|admin| !admin.is_empty())
+            .collect();
+
+        // Discovery: the provider publishes its endpoints and signing keys
+        // at /.well-known/openid-configuration
+        let metadata = CoreProviderMetadata::discover_async(issuer, async_http_client)
+            .await
+            .map_err(|e| format!("Discovery failed: {e}"))?;
+        let client = CoreClient::from_provider_metadata(metadata, client_id, client_secret)
+            .set_redirect_uri(redirect_url);
+
+        Ok(Some(Self {
+            client,
+            admins,
+            pending: Mutex::new(HashMap::new()),
+        }))
+    }
+
+    fn role(&self, verified_email: Option<&str>, subject: &str) -> LoginRole {
+        let is_admin = self.admins.iter().any(|admin| {
+            verified_email.is_some_and(|email| email.eq_ignore_ascii_case(admin)) || subject == admin
+        });
+        if is_admin {
+            LoginRole::Admin
+        } else {
+            LoginRole::User
+        }
+    }
+}
+
+/// Step 1: send the user to the provider's login page.
+pub async fn login(Extension(oauth): Extension<SharedOAuth>) -> Redirect {
+    // PKCE ties the code we get back to this request, so an intercepted
+    // code is useless on its own
+    let (pkce_challenge, pkce_verifier) = PkceCodeChallenge::new_random_sha256();
+    let (auth_url, csrf_state, nonce) = oauth
+        .client
+        .authorize_url(
+            CoreAuthenticationFlow::AuthorizationCode,
+            CsrfToken::new_random,
+            Nonce::new_random,
+        )
+        .add_scope(Scope::new("email".to_string()))
+        .add_scope(Scope::new("profile".to_string()))
+        .set_pkce_challenge(pkce_challenge)
+        .url();
+
+    let mut pending = oauth.pending.lock().unwrap();
+    pending.retain(|_, login| login.started.elapsed() < LOGIN_TIMEOUT);
+    pending.insert(
+        csrf_state.secret().clone(),
+        PendingLogin {
+            pkce_verifier,
+            nonce,
+            started: Instant::now(),
+        },
+    );
+    Redirect::to(auth_url.as_str())
+}
+
+#[derive(Deserialize)]
+pub struct Callback {
+    code: String,
+    state: String,
+}
+
+/// Step 2: the provider sends the user back here with a code, which we
+/// swap for tokens and check.
+pub async fn callback(
+    Extension(oauth): Extension<SharedOAuth>,
+    Extension(sessions): Extension<Sessions>,
+    Query(callback): Query<Callback>,
+) -> Result<Json<LoginResponse>, ApiError> {
+    // An unknown state means this callback isn't for a login we started
+    let login = oauth
+        .pending
+        .lock()
+        .unwrap()
+        .remove(&callback.state)
+        .filter(|login| login.started.elapsed() < LOGIN_TIMEOUT)
+        .ok_or_else(|| ApiError::new(StatusCode::BAD_REQUEST, "Unknown or expired login"))?;
+
+    let token_response = oauth
+        .client
+        .exchange_code(AuthorizationCode::new(callback.code))
+        .set_pkce_verifier(login.pkce_verifier)
+        .request_async(async_http_client)
+        .await
+        .map_err(|e| {
+            tracing::warn!("Token exchange failed: {e}");
+            ApiError::new(StatusCode::BAD_GATEWAY, "Unable to complete the login")
+        })?;
+
+    // The ID token is a JWT signed by the provider. This checks the
+    // signature, issuer, audience, expiry and that the nonce is ours.
+    let id_token = token_response
+        .id_token()
+        .ok_or_else(|| ApiError::new(StatusCode::BAD_GATEWAY, "The provider sent no ID token"))?;
+    let claims = id_token
+        .claims(&oauth.client.id_token_verifier(), &login.nonce)
+        .map_err(|e| {
+            tracing::warn!("Invalid ID token: {e}");
+            ApiError::new(StatusCode::UNAUTHORIZED, "Invalid ID token")
+        })?;
+
+    // Make sure the access token is the one the ID token was issued with
+    if let Some(expected) = claims.access_token_hash() {
+        let actual = id_token
+            .signing_alg()
+            .and_then(|alg| AccessTokenHash::from_token(token_response.access_token(), &alg))
+            .map_err(|_| ApiError::new(StatusCode::UNAUTHORIZED, "Invalid access token"))?;
+        if actual != *expected {
+            return Err(ApiError::new(StatusCode::UNAUTHORIZED, "Invalid access token"));
+        }
+    }
+
+    // Some providers let users type in any email, so only trust checked ones
+    let email = claims
+        .email()
+        .filter(|_| claims.email_verified() == Some(true))
+        .map(|email| email.as_str());
+    let subject = claims.subject().as_str();
+    let role = oauth.role(email, subject);
+    let username = email.unwrap_or(subject).to_string();
+
+    tracing::info!(%username, %role, "OpenID Connect login granted");
+    let token = uuid::Uuid::new_v4().to_string();
+    sessions.write().unwrap().insert(
+        token.clone(),
+        Session {
+            username,
+            role: role.clone(),
+        },
+    );
+    Ok(Json(LoginResponse { token, role }))
+}
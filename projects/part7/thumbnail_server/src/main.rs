@@ -13,6 +13,8 @@ use tokio_util::io::ReaderStream;
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
+    telemetry::init("info");
+
     // Read the .env file and obtain the database URL
     dotenv::dotenv()?;
     let db_url = std::env::var("DATABASE_URL")?;
@@ -36,6 +38,7 @@ async fn main() -> anyhow::Result<()> {
         .route("/search", post(search_images))
         .layer(Extension(pool));
     let addr = SocketAddr::from(([127, 0, 0, 1], 3000));
+    tracing::info!("Listening on http://{addr}");
     axum::Server::bind(&addr)
         .serve(app.into_make_service())
         .await
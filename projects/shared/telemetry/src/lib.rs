@@ -0,0 +1,106 @@
+//! Logging setup shared by the login tools and web servers, so they all
+//! read the same environment variables:
+//!
+//! * `RUST_LOG` picks what is recorded, e.g. `debug` or `login_web=trace`.
+//! * `LOG_FORMAT` is `full` (the default), `pretty`, or `json` for one
+//!   JSON object per line.
+//!
+//! Logs go to stderr, so they never mix with a program's real output.
+use http::Request;
+use std::{io::IsTerminal, panic};
+use tracing::Span;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Format {
+    Full,
+    Pretty,
+    Json,
+}
+
+impl Format {
+    /// Reads `LOG_FORMAT`. Anything unrecognized gets the default.
+    pub fn from_env() -> Self {
+        Self::from_name(&std::env::var("LOG_FORMAT").unwrap_or_default())
+    }
+
+    fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "pretty" => Format::Pretty,
+            "json" => Format::Json,
+            _ => Format::Full,
+        }
+    }
+}
+
+/// Sets up logging for the whole program. `default_filter` is used when
+/// `RUST_LOG` isn't set: command line tools usually want `warn`, servers
+/// `info`. Call it once, at the top of `main`.
+pub fn init(default_filter: &str) {
+    let filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(default_filter));
+    let subscriber = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal());
+    match Format::from_env() {
+        Format::Full => subscriber.init(),
+        Format::Pretty => subscriber.pretty().init(),
+        Format::Json => subscriber.json().init(),
+    }
+    install_panic_hook();
+}
+
+/// One span per request, tagged with the ID from `SetRequestIdLayer` (so
+/// that layer has to run first). Pass it to `TraceLayer::make_span_with`.
+pub fn make_span<B>(request: &Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get("x-request-id")
+        .and_then(|id| id.to_str().ok())
+        .unwrap_or("-");
+    tracing::info_span!(
+        "request",
+        method = %request.method(),
+        path = %request.uri().path(),
+        request_id,
+    )
+}
+
+/// Panics are logged like everything else, so they show up in the same
+/// place (and format) as the events that led up to them.
+fn install_panic_hook() {
+    panic::set_hook(Box::new(|info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+            .unwrap_or("Box<dyn Any>");
+        let thread = std::thread::current();
+        let thread = thread.name().unwrap_or("<unnamed>");
+        match info.location() {
+            Some(location) => tracing::error!(
+                panic.file = location.file(),
+                panic.line = location.line(),
+                panic.thread = thread,
+                "panicked: {message}"
+            ),
+            None => tracing::error!(panic.thread = thread, "panicked: {message}"),
+        }
+    }));
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_format_names() {
+        assert_eq!(Format::from_name("json"), Format::Json);
+        assert_eq!(Format::from_name("JSON"), Format::Json);
+        assert_eq!(Format::from_name("pretty"), Format::Pretty);
+        assert_eq!(Format::from_name(""), Format::Full);
+        assert_eq!(Format::from_name("xml"), Format::Full);
+    }
+}
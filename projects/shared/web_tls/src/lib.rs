@@ -0,0 +1,163 @@
+//! HTTPS setup shared by the web servers, so they all read the same
+//! environment variables and redirect plain HTTP the same way.
+use anyhow::{bail, Context};
+use axum::{
+    extract::Host,
+    http::{uri::Authority, StatusCode, Uri},
+    response::Redirect,
+    Extension, Router,
+};
+use axum_server::tls_rustls::RustlsConfig;
+use std::{future::Future, net::SocketAddr, path::PathBuf};
+
+/// Reads an environment variable. Empty counts as unset; some
+/// orchestrators pass blank variables through.
+pub fn env_var(name: &str) -> Option<String> {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+/// Parses a port number, naming the setting it came from if it's no good.
+pub fn parse_port(name: &str, port: &str) -> anyhow::Result<u16> {
+    let port: u16 = port
+        .parse()
+        .with_context(|| format!("{name} `{port}` is not a number from 1 to 65535"))?;
+    if port == 0 {
+        bail!("{name} must be from 1 to 65535");
+    }
+    Ok(port)
+}
+
+/// HTTPS is on when HTTPS_PORT is set. The certificate and key are PEM
+/// files named by TLS_CERT_PATH and TLS_KEY_PATH; without them, a
+/// self-signed certificate is made up at startup, which is fine for
+/// trying things out locally and nothing else.
+///
+/// While HTTPS is on, the plain HTTP port only redirects to it. Set
+/// HTTP_REDIRECT=false to not listen on HTTP at all.
+pub struct TlsSettings {
+    pub port: u16,
+    pub redirect_http: bool,
+    cert_and_key: Option<(PathBuf, PathBuf)>,
+}
+
+impl TlsSettings {
+    pub fn from_env() -> anyhow::Result<Option<Self>> {
+        let Some(port) = env_var("HTTPS_PORT") else {
+            return Ok(None);
+        };
+        let port = parse_port("HTTPS_PORT", &port)?;
+        let cert_and_key = match (env_var("TLS_CERT_PATH"), env_var("TLS_KEY_PATH")) {
+            (Some(cert), Some(key)) => Some((cert.into(), key.into())),
+            (None, None) => None,
+            _ => bail!("Set both TLS_CERT_PATH and TLS_KEY_PATH, or neither"),
+        };
+        let redirect_http = !env_var("HTTP_REDIRECT")
+            .is_some_and(|value| value.eq_ignore_ascii_case("false"));
+        Ok(Some(Self {
+            port,
+            redirect_http,
+            cert_and_key,
+        }))
+    }
+
+    pub async fn rustls_config(&self) -> anyhow::Result<RustlsConfig> {
+        match &self.cert_and_key {
+            Some((cert, key)) => RustlsConfig::from_pem_file(cert, key)
+                .await
+                .with_context(|| {
+                    format!("Unable to load {} and {}", cert.display(), key.display())
+                }),
+            None => {
+                tracing::warn!("TLS_CERT_PATH is not set, using a self-signed development certificate");
+                let (cert, key) = self_signed_certificate()?;
+                Ok(RustlsConfig::from_pem(cert, key).await?)
+            }
+        }
+    }
+}
+
+/// Makes a certificate for `localhost`, returned as (certificate, key) PEM.
+/// Browsers will warn about it, since nobody they trust signed it.
+pub fn self_signed_certificate() -> anyhow::Result<(Vec<u8>, Vec<u8>)> {
+    let names = vec!["localhost".to_string(), "127.0.0.1".to_string()];
+    let cert = rcgen::generate_simple_self_signed(names)?;
+    let cert_pem = cert.serialize_pem()?;
+    let key_pem = cert.serialize_private_key_pem();
+    Ok((cert_pem.into_bytes(), key_pem.into_bytes()))
+}
+
+async fn redirect(
+    Host(host): Host,
+    Extension(https_port): Extension<u16>,
+    uri: Uri,
+) -> Result<Redirect, StatusCode> {
+    // Keep the host the client asked for, but swap in the HTTPS port
+    let authority: Authority = host.parse().map_err(|_| StatusCode::BAD_REQUEST)?;
+    let host = authority.host();
+    let path = uri.path_and_query().map(|p| p.as_str()).unwrap_or("/");
+    let location = if https_port == 443 {
+        format!("https://{host}{path}")
+    } else {
+        format!("https://{host}:{https_port}{path}")
+    };
+    Ok(Redirect::permanent(&location))
+}
+
+/// A server answering every plain HTTP request on `addr` with a redirect
+/// to HTTPS, until `shutdown` resolves. Binds straight away, so a port
+/// clash is reported at startup.
+pub fn redirect_server(
+    addr: SocketAddr,
+    https_port: u16,
+    shutdown: impl Future<Output = ()>,
+) -> anyhow::Result<impl Future<Output = anyhow::Result<()>>> {
+    let server = axum::Server::try_bind(&addr)
+        .with_context(|| format!("Unable to listen on {addr}"))?
+        .serve(redirect_app(https_port).into_make_service())
+        .with_graceful_shutdown(shutdown);
+    Ok(async move {
+        server.await?;
+        Ok(())
+    })
+}
+
+fn redirect_app(https_port: u16) -> Router {
+    Router::new()
+        .fallback(redirect)
+        .layer(Extension(https_port))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{body::Body, http::{header, Request}};
+    use tower::ServiceExt;
+
+    async fn location(https_port: u16, host: &str, uri: &str) -> String {
+        let request = Request::builder()
+            .uri(uri)
+            .header(header::HOST, host)
+            .body(Body::empty())
+            .unwrap();
+        let response = redirect_app(https_port).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+        response.headers()[header::LOCATION].to_str().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_redirect_keeps_host_and_path() {
+        assert_eq!(location(443, "example.com", "/a?b=c").await, "https://example.com/a?b=c");
+        assert_eq!(location(3443, "localhost:3001", "/").await, "https://localhost:3443/");
+    }
+
+    #[test]
+    fn test_parse_port() {
+        assert_eq!(parse_port("PORT", "8080").unwrap(), 8080);
+        assert!(parse_port("PORT", "0").is_err());
+        assert!(parse_port("PORT", "65536").is_err());
+        assert!(parse_port("PORT", "http").is_err());
+    }
+}
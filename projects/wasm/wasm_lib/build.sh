@@ -1,5 +1,10 @@
 #!/bin/bash
-cargo build --release --target wasm32-unknown-unknown
+# Pass --simd to build the SIMD version of sum_simd
+if [ "$1" == "--simd" ]; then
+    RUSTFLAGS="-C target-feature=+simd128" cargo build --release --target wasm32-unknown-unknown --features simd
+else
+    cargo build --release --target wasm32-unknown-unknown
+fi
 mkdir -p out
 wasm-bindgen target/wasm32-unknown-unknown/release/wasm_lib.wasm --out-dir out --no-modules --no-typescript
 cp out/* ../wasm_web_server/web/
@@ -0,0 +1,41 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{Document, Element};
+
+fn document() -> Result<Document, JsValue> {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("No document available"))
+}
+
+fn element_by_id(document: &Document, element_id: &str) -> Result<Element, JsValue> {
+    document
+        .get_element_by_id(element_id)
+        .ok_or_else(|| JsValue::from_str(&format!("No element with id '{element_id}'")))
+}
+
+/// Replaces the text inside an element.
+#[wasm_bindgen]
+pub fn set_text(element_id: &str, text: &str) -> Result<(), JsValue> {
+    let element = element_by_id(&document()?, element_id)?;
+    element.set_text_content(Some(text));
+    Ok(())
+}
+
+/// Replaces the contents of an element with a bulleted list of `items`.
+#[wasm_bindgen]
+pub fn render_list(element_id: &str, items: Vec<String>) -> Result<(), JsValue> {
+    let document = document()?;
+    let element = element_by_id(&document, element_id)?;
+
+    let list = document.create_element("ul")?;
+    for item in items {
+        let list_item = document.create_element("li")?;
+        // set_text_content escapes the text, unlike set_inner_html
+        list_item.set_text_content(Some(&item));
+        list.append_child(&list_item)?;
+    }
+
+    element.set_text_content(None);
+    element.append_child(&list)?;
+    Ok(())
+}
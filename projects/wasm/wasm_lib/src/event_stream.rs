@@ -0,0 +1,56 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{EventSource, MessageEvent};
+
+type Listener = Closure<dyn FnMut(MessageEvent)>;
+
+/// Listens to a Server-Sent Events endpoint, usable from JS:
+///
+/// ```js
+/// let events = wasm_bindgen.EventStream.connect("/events");
+/// events.on_event("counter", (data) => console.log(data));
+/// ```
+#[wasm_bindgen]
+pub struct EventStream {
+    source: EventSource,
+    // Kept here so the callbacks live as long as the stream does
+    listeners: Vec<(String, Listener)>,
+}
+
+#[wasm_bindgen]
+impl EventStream {
+    pub fn connect(url: &str) -> Result<EventStream, JsValue> {
+        let source = EventSource::new(url)?;
+        Ok(Self {
+            source,
+            listeners: Vec::new(),
+        })
+    }
+
+    /// Calls `callback` with the data of every event named `event_type`.
+    /// Events sent without a name are called "message".
+    pub fn on_event(&mut self, event_type: &str, callback: js_sys::Function) -> Result<(), JsValue> {
+        let closure = Listener::new(move |event: MessageEvent| {
+            let _ = callback.call1(&JsValue::NULL, &event.data());
+        });
+        self.source
+            .add_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref())?;
+        self.listeners.push((event_type.to_string(), closure));
+        Ok(())
+    }
+
+    pub fn close(&self) {
+        self.source.close();
+    }
+}
+
+impl Drop for EventStream {
+    fn drop(&mut self) {
+        // Unhook the listeners before the closures they point to are freed
+        for (event_type, closure) in &self.listeners {
+            let _ = self
+                .source
+                .remove_event_listener_with_callback(event_type, closure.as_ref().unchecked_ref());
+        }
+        self.source.close();
+    }
+}
@@ -0,0 +1,211 @@
+use std::{cell::RefCell, rc::Rc};
+use wasm_bindgen::prelude::*;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, MouseEvent};
+
+const CELL_SIZE: f64 = 8.0;
+
+#[wasm_bindgen]
+pub struct GameOfLife {
+    width: u32,
+    height: u32,
+    cells: Vec<bool>,
+}
+
+impl GameOfLife {
+    // A board needs at least one cell each way for the neighbor wrapping to work
+    fn blank(width: u32, height: u32) -> Self {
+        let (width, height) = (width.max(1), height.max(1));
+        Self {
+            width,
+            height,
+            cells: vec![false; (width * height) as usize],
+        }
+    }
+
+    fn index(&self, row: u32, col: u32) -> usize {
+        (row * self.width + col) as usize
+    }
+
+    fn live_neighbor_count(&self, row: u32, col: u32) -> u8 {
+        let mut count = 0;
+        // Adding height-1 / width-1 and wrapping is "minus one" without underflow
+        for delta_row in [self.height - 1, 0, 1] {
+            for delta_col in [self.width - 1, 0, 1] {
+                if delta_row == 0 && delta_col == 0 {
+                    continue;
+                }
+                let neighbor_row = (row + delta_row) % self.height;
+                let neighbor_col = (col + delta_col) % self.width;
+                count += self.cells[self.index(neighbor_row, neighbor_col)] as u8;
+            }
+        }
+        count
+    }
+}
+
+#[wasm_bindgen]
+impl GameOfLife {
+    /// Creates a board with a repeatable starting pattern. A zero width or
+    /// height is treated as one.
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: u32, height: u32) -> Self {
+        let mut game = Self::blank(width, height);
+        for (i, cell) in game.cells.iter_mut().enumerate() {
+            *cell = i % 2 == 0 || i % 7 == 0;
+        }
+        game
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn is_alive(&self, row: u32, col: u32) -> bool {
+        row < self.height && col < self.width && self.cells[self.index(row, col)]
+    }
+
+    pub fn toggle_cell(&mut self, row: u32, col: u32) {
+        if row < self.height && col < self.width {
+            let idx = self.index(row, col);
+            self.cells[idx] = !self.cells[idx];
+        }
+    }
+
+    /// Advances the game by one generation.
+    pub fn tick(&mut self) {
+        let mut next = self.cells.clone();
+        for row in 0..self.height {
+            for col in 0..self.width {
+                let idx = self.index(row, col);
+                next[idx] = matches!(
+                    (self.cells[idx], self.live_neighbor_count(row, col)),
+                    (true, 2) | (true, 3) | (false, 3)
+                );
+            }
+        }
+        self.cells = next;
+    }
+
+    pub fn render(&self, ctx: &CanvasRenderingContext2d) {
+        ctx.set_fill_style(&JsValue::from_str("#ffffff"));
+        ctx.fill_rect(
+            0.0,
+            0.0,
+            self.width as f64 * CELL_SIZE,
+            self.height as f64 * CELL_SIZE,
+        );
+
+        ctx.set_fill_style(&JsValue::from_str("#000000"));
+        for row in 0..self.height {
+            for col in 0..self.width {
+                if self.is_alive(row, col) {
+                    ctx.fill_rect(
+                        col as f64 * CELL_SIZE,
+                        row as f64 * CELL_SIZE,
+                        CELL_SIZE,
+                        CELL_SIZE,
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn request_animation_frame(f: &Closure<dyn FnMut()>) -> Result<i32, JsValue> {
+    web_sys::window()
+        .ok_or_else(|| JsValue::from_str("No window available"))?
+        .request_animation_frame(f.as_ref().unchecked_ref())
+}
+
+/// Sizes the canvas to fit a new game, then ticks and draws it every
+/// animation frame. Clicking a cell toggles it.
+#[wasm_bindgen]
+pub fn run_game_of_life(canvas_id: &str, width: u32, height: u32) -> Result<(), JsValue> {
+    let canvas: HtmlCanvasElement = web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.get_element_by_id(canvas_id))
+        .ok_or_else(|| JsValue::from_str(&format!("No element with id '{canvas_id}'")))?
+        .dyn_into()?;
+
+    // Both the click handler and the animation loop need the game
+    let game = Rc::new(RefCell::new(GameOfLife::new(width, height)));
+    canvas.set_width(game.borrow().width() * CELL_SIZE as u32);
+    canvas.set_height(game.borrow().height() * CELL_SIZE as u32);
+    let ctx: CanvasRenderingContext2d = canvas
+        .get_context("2d")?
+        .ok_or_else(|| JsValue::from_str("Canvas has no 2d context"))?
+        .dyn_into()?;
+
+    let click_game = game.clone();
+    let on_click = Closure::<dyn FnMut(MouseEvent)>::new(move |event: MouseEvent| {
+        let row = (event.offset_y() as f64 / CELL_SIZE) as u32;
+        let col = (event.offset_x() as f64 / CELL_SIZE) as u32;
+        click_game.borrow_mut().toggle_cell(row, col);
+    });
+    canvas.add_event_listener_with_callback("click", on_click.as_ref().unchecked_ref())?;
+    // The listener lives as long as the page, so hand it over to JS
+    on_click.forget();
+
+    // The frame callback has to schedule itself, so it keeps a handle to
+    // the cell it's stored in.
+    let frame = Rc::new(RefCell::new(None::<Closure<dyn FnMut()>>));
+    let next_frame = frame.clone();
+    *frame.borrow_mut() = Some(Closure::new(move || {
+        let mut game = game.borrow_mut();
+        game.tick();
+        game.render(&ctx);
+        if let Some(callback) = next_frame.borrow().as_ref() {
+            let _ = request_animation_frame(callback);
+        }
+    }));
+
+    let first_frame = frame.borrow();
+    request_animation_frame(first_frame.as_ref().unwrap())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_blinker() {
+        let mut game = GameOfLife::blank(5, 5);
+        game.toggle_cell(2, 1);
+        game.toggle_cell(2, 2);
+        game.toggle_cell(2, 3);
+
+        game.tick();
+        assert!(game.is_alive(1, 2) && game.is_alive(2, 2) && game.is_alive(3, 2));
+        assert!(!game.is_alive(2, 1) && !game.is_alive(2, 3));
+
+        game.tick();
+        assert!(game.is_alive(2, 1) && game.is_alive(2, 2) && game.is_alive(2, 3));
+    }
+
+    #[test]
+    fn test_is_alive_out_of_bounds() {
+        let mut game = GameOfLife::blank(3, 2);
+        game.toggle_cell(1, 2);
+        assert!(game.is_alive(1, 2));
+        assert!(!game.is_alive(2, 0));
+        assert!(!game.is_alive(0, 3));
+        assert!(!game.is_alive(u32::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn test_zero_size_board() {
+        let mut game = GameOfLife::new(0, 4);
+        assert_eq!((game.width(), game.height()), (1, 4));
+        game.tick();
+
+        let mut game = GameOfLife::new(0, 0);
+        assert_eq!((game.width(), game.height()), (1, 1));
+        game.tick();
+        assert!(!game.is_alive(0, 0));
+    }
+}
@@ -1,5 +1,13 @@
 use wasm_bindgen::prelude::*;
 
+mod dom;
+mod event_stream;
+mod game_of_life;
+mod simd;
+mod storage;
+mod timer;
+mod ws_client;
+
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -23,12 +31,13 @@ pub fn greet(s: String) -> String {
 
 #[wasm_bindgen]
 pub fn sum(arr: &[i32]) -> i32 {
-    arr.iter().sum()
+    // Wraps on overflow like sum_simd, rather than panicking in debug builds
+    arr.iter().fold(0, |total, n| total.wrapping_add(*n))
 }
 
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 #[wasm_bindgen]
 pub struct Person {
     name: String,
@@ -56,37 +65,160 @@ impl Person {
 }
 
 #[wasm_bindgen]
-pub fn serialize_person(person: &Person) -> String {
-    serde_json::to_string(person).unwrap()
+pub fn serialize_person(person: &Person) -> Result<String, JsError> {
+    Ok(serde_json::to_string(person)?)
+}
+
+// Convert to and from plain JS objects, so JS doesn't need to call JSON.parse
+#[wasm_bindgen]
+pub fn person_to_js(person: &Person) -> JsValue {
+    serde_wasm_bindgen::to_value(person).unwrap_throw()
+}
+
+#[wasm_bindgen]
+pub fn person_from_js(value: JsValue) -> Result<Person, JsError> {
+    serde_wasm_bindgen::from_value(value).map_err(|e| JsError::new(&format!("Not a valid Person: {e}")))
+}
+
+// A compact binary encoding, to compare against JSON. The server uses the
+// same bincode version, so it can decode these bytes directly.
+#[wasm_bindgen]
+pub fn person_to_bincode(person: &Person) -> Result<Vec<u8>, JsError> {
+    Ok(bincode::serialize(person)?)
+}
+
+#[wasm_bindgen]
+pub fn person_from_bincode(bytes: &[u8]) -> Result<Person, JsError> {
+    Ok(bincode::deserialize(bytes)?)
+}
+
+// Arrays of people cross the boundary as plain JS arrays of objects
+fn people_from_js(people: JsValue) -> Result<Vec<Person>, JsError> {
+    serde_wasm_bindgen::from_value(people)
+        .map_err(|e| JsError::new(&format!("Expected an array of people: {e}")))
+}
+
+#[wasm_bindgen]
+pub fn oldest_person(people: JsValue) -> Result<JsValue, JsError> {
+    let people = people_from_js(people)?;
+    Ok(people
+        .iter()
+        .max_by_key(|person| person.age)
+        .map(person_to_js)
+        .unwrap_or(JsValue::UNDEFINED))
+}
+
+#[wasm_bindgen]
+pub fn sort_people_by_age(people: JsValue) -> Result<JsValue, JsError> {
+    let mut people = people_from_js(people)?;
+    people.sort_by_key(|person| person.age);
+    serde_wasm_bindgen::to_value(&people).map_err(|e| JsError::new(&e.to_string()))
 }
 
 use wasm_bindgen_futures::JsFuture;
-use web_sys::{Request, RequestInit, RequestMode, Response};
+use web_sys::{Request, RequestInit, RequestMode, Response, Window, WorkerGlobalScope};
+
+// Turn a thrown JS value into an error with a readable message
+fn js_error(context: &str, err: JsValue) -> JsError {
+    let detail = err
+        .dyn_ref::<js_sys::Error>()
+        .map(|e| String::from(e.message()))
+        .or_else(|| err.as_string())
+        .unwrap_or_else(|| format!("{err:?}"));
+    JsError::new(&format!("{context}: {detail}"))
+}
 
 #[wasm_bindgen]
-pub async fn fetch_hello_json() -> Result<JsValue, JsValue> {
+extern "C" {
+    type Global;
+
+    #[wasm_bindgen(method, getter, js_name = Window)]
+    fn window(this: &Global) -> JsValue;
+
+    #[wasm_bindgen(method, getter, js_name = WorkerGlobalScope)]
+    fn worker(this: &Global) -> JsValue;
+}
+
+// Runs `fetch` from either a page or a web worker, and turns HTTP
+// error statuses (which `fetch` itself treats as success) into errors.
+async fn fetch(request: &Request) -> Result<Response, JsError> {
+    // Checking for the constructors avoids `instanceof Window` throwing
+    // a ReferenceError inside workers.
+    let global: Global = js_sys::global().unchecked_into();
+    let promise = if !global.window().is_undefined() {
+        global.unchecked_into::<Window>().fetch_with_request(request)
+    } else if !global.worker().is_undefined() {
+        global.unchecked_into::<WorkerGlobalScope>().fetch_with_request(request)
+    } else {
+        return Err(JsError::new("fetch requires a window or web worker context"));
+    };
+
+    let resp_value = JsFuture::from(promise)
+        .await
+        .map_err(|e| js_error(&format!("Request to {} failed", request.url()), e))?;
+    let resp: Response = resp_value
+        .dyn_into()
+        .map_err(|_| JsError::new("fetch did not return a Response"))?;
+
+    if !resp.ok() {
+        return Err(JsError::new(&format!(
+            "{} {} returned {} {}",
+            request.method(),
+            request.url(),
+            resp.status(),
+            resp.status_text()
+        )));
+    }
+    Ok(resp)
+}
+
+async fn response_json(resp: Response) -> Result<JsValue, JsError> {
+    let promise = resp
+        .json()
+        .map_err(|e| js_error("Unable to read the response body", e))?;
+    JsFuture::from(promise)
+        .await
+        .map_err(|e| js_error("The response wasn't valid JSON", e))
+}
+
+#[wasm_bindgen]
+pub async fn fetch_hello_json() -> Result<JsValue, JsError> {
     let mut opts = RequestInit::new();
     opts.method("GET");
     opts.mode(RequestMode::Cors);
 
-    let url = format!("/json");
-
-    let request = Request::new_with_str_and_init(&url, &opts)?;
-
+    let request = Request::new_with_str_and_init("/json", &opts)
+        .map_err(|e| js_error("Unable to create the request", e))?;
     request
         .headers()
-        .set("Accept", "application/vnd.github.v3+json")?;
+        .set("Accept", "application/json")
+        .map_err(|e| js_error("Unable to set request headers", e))?;
 
-    let window = web_sys::window().unwrap();
-    let resp_value = JsFuture::from(window.fetch_with_request(&request)).await?;
+    let resp = fetch(&request).await?;
 
-    // `resp_value` is a `Response` object.
-    assert!(resp_value.is_instance_of::<Response>());
-    let resp: Response = resp_value.dyn_into().unwrap();
+    // Send the JSON response back to JS.
+    response_json(resp).await
+}
 
-    // Convert this other `Promise` into a rust `Future`.
-    let json = JsFuture::from(resp.json()?).await?;
+#[wasm_bindgen]
+pub async fn fetch_post_json(url: String, body: JsValue) -> Result<JsValue, JsError> {
+    // Turn the JS object into a JSON string for the request body
+    let body = js_sys::JSON::stringify(&body)
+        .map_err(|e| js_error("Unable to serialize the request body", e))?;
 
-    // Send the JSON response back to JS.
-    Ok(json)
-}
\ No newline at end of file
+    let mut opts = RequestInit::new();
+    opts.method("POST");
+    opts.mode(RequestMode::Cors);
+    opts.body(Some(&body));
+
+    let request = Request::new_with_str_and_init(&url, &opts)
+        .map_err(|e| js_error("Unable to create the request", e))?;
+    let headers = request.headers();
+    headers
+        .set("Content-Type", "application/json")
+        .and_then(|_| headers.set("Accept", "application/json"))
+        .map_err(|e| js_error("Unable to set request headers", e))?;
+
+    let resp = fetch(&request).await?;
+    response_json(resp).await
+}
@@ -0,0 +1,99 @@
+use serde::Serialize;
+use wasm_bindgen::prelude::*;
+
+// WebAssembly has no runtime feature detection: a module containing SIMD
+// instructions won't even load in a browser that lacks them. So the choice
+// is made when building. Build with the `simd` feature *and*
+// RUSTFLAGS="-C target-feature=+simd128" to get the vectorized version;
+// any other build quietly uses the scalar loop.
+#[cfg(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))]
+fn sum_i32(arr: &[i32]) -> i32 {
+    use core::arch::wasm32::*;
+
+    let mut chunks = arr.chunks_exact(4);
+    let mut totals = i32x4_splat(0);
+    for chunk in &mut chunks {
+        // Safety: the chunk is exactly 16 bytes, and wasm loads don't need alignment
+        let values = unsafe { v128_load(chunk.as_ptr() as *const v128) };
+        totals = i32x4_add(totals, values);
+    }
+
+    let lanes = i32x4_extract_lane::<0>(totals)
+        .wrapping_add(i32x4_extract_lane::<1>(totals))
+        .wrapping_add(i32x4_extract_lane::<2>(totals))
+        .wrapping_add(i32x4_extract_lane::<3>(totals));
+    chunks
+        .remainder()
+        .iter()
+        .fold(lanes, |total, n| total.wrapping_add(*n))
+}
+
+#[cfg(not(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128")))]
+fn sum_i32(arr: &[i32]) -> i32 {
+    arr.iter().fold(0, |total, n| total.wrapping_add(*n))
+}
+
+#[wasm_bindgen]
+pub fn simd_enabled() -> bool {
+    cfg!(all(feature = "simd", target_arch = "wasm32", target_feature = "simd128"))
+}
+
+#[wasm_bindgen]
+pub fn sum_simd(arr: &[i32]) -> i32 {
+    sum_i32(arr)
+}
+
+#[derive(Serialize)]
+struct BenchmarkResult {
+    len: usize,
+    iterations: u32,
+    scalar_ms: f64,
+    simd_ms: f64,
+    simd_enabled: bool,
+}
+
+fn now_ms() -> f64 {
+    web_sys::window()
+        .and_then(|window| window.performance())
+        .map(|performance| performance.now())
+        .unwrap_or_else(js_sys::Date::now)
+}
+
+fn time_ms(iterations: u32, f: impl Fn() -> i32) -> f64 {
+    let start = now_ms();
+    for _ in 0..iterations {
+        // black_box stops the optimizer from skipping work whose result is unused
+        std::hint::black_box(f());
+    }
+    now_ms() - start
+}
+
+/// Sums a `len` element array with both versions, returning the timings.
+#[wasm_bindgen]
+pub fn benchmark_sums(len: usize) -> Result<JsValue, JsError> {
+    const ITERATIONS: u32 = 100;
+    let numbers: Vec<i32> = (0..len).map(|i| (i % 100) as i32).collect();
+
+    let result = BenchmarkResult {
+        len,
+        iterations: ITERATIONS,
+        scalar_ms: time_ms(ITERATIONS, || crate::sum(std::hint::black_box(&numbers))),
+        simd_ms: time_ms(ITERATIONS, || sum_simd(std::hint::black_box(&numbers))),
+        simd_enabled: simd_enabled(),
+    };
+    Ok(serde_wasm_bindgen::to_value(&result)?)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sum_matches_scalar() {
+        let numbers: Vec<i32> = (0..1003).collect();
+        assert_eq!(sum_simd(&numbers), crate::sum(&numbers));
+        assert_eq!(sum_simd(&[]), 0);
+        let overflowing = [i32::MAX, 1, 0, 0, i32::MAX];
+        assert_eq!(sum_simd(&overflowing), crate::sum(&overflowing));
+    }
+}
@@ -0,0 +1,32 @@
+use wasm_bindgen::prelude::*;
+use web_sys::Storage;
+use crate::Person;
+
+// localStorage can be missing (no window, e.g. in a worker) or disabled
+// by the browser's privacy settings, so this is an Option rather than an error.
+fn local_storage() -> Option<Storage> {
+    web_sys::window()?.local_storage().ok()?
+}
+
+#[wasm_bindgen]
+pub fn storage_available() -> bool {
+    local_storage().is_some()
+}
+
+#[wasm_bindgen]
+pub fn save_person(key: &str, person: &Person) -> Result<(), JsError> {
+    let storage = local_storage().ok_or_else(|| JsError::new("localStorage is not available"))?;
+    let json = serde_json::to_string(person)?;
+    storage
+        .set_item(key, &json)
+        // Usually means the storage quota has been used up
+        .map_err(|_| JsError::new(&format!("Unable to save '{key}' to localStorage")))
+}
+
+/// Returns the person saved under `key`, or `undefined` if there isn't one
+/// (or storage isn't available, or the saved value isn't a valid Person).
+#[wasm_bindgen]
+pub fn load_person(key: &str) -> Option<Person> {
+    let json = local_storage()?.get_item(key).ok()??;
+    serde_json::from_str(&json).ok()
+}
@@ -0,0 +1,52 @@
+use wasm_bindgen::prelude::*;
+
+/// Returned by `set_interval_callback`. The interval keeps running for as
+/// long as this handle exists; call `cancel()` (or `free()`) to stop it.
+#[wasm_bindgen]
+pub struct IntervalHandle {
+    id: i32,
+    // The browser only holds a reference to this closure's JS wrapper. If
+    // Rust dropped the closure while the interval was still registered, the
+    // next tick would call freed memory and throw. Keeping it here ties the
+    // closure's lifetime to the handle.
+    _closure: Closure<dyn FnMut()>,
+}
+
+#[wasm_bindgen]
+impl IntervalHandle {
+    /// Stops the interval. Takes `self`, so the handle can't be used again.
+    pub fn cancel(self) {
+        // Drop does the work
+    }
+}
+
+impl Drop for IntervalHandle {
+    fn drop(&mut self) {
+        // Unregister before the closure is freed
+        if let Some(window) = web_sys::window() {
+            window.clear_interval_with_handle(self.id);
+        }
+    }
+}
+
+/// Calls the JS function `callback` every `ms` milliseconds, passing it
+/// the number of times it has been called.
+#[wasm_bindgen]
+pub fn set_interval_callback(ms: i32, callback: js_sys::Function) -> Result<IntervalHandle, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window available"))?;
+
+    let mut count: u32 = 0;
+    let closure = Closure::<dyn FnMut()>::new(move || {
+        count += 1;
+        let _ = callback.call1(&JsValue::NULL, &JsValue::from(count));
+    });
+
+    let id = window.set_interval_with_callback_and_timeout_and_arguments_0(
+        closure.as_ref().unchecked_ref(),
+        ms,
+    )?;
+    Ok(IntervalHandle {
+        id,
+        _closure: closure,
+    })
+}
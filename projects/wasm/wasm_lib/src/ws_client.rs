@@ -0,0 +1,61 @@
+use wasm_bindgen::prelude::*;
+use web_sys::{MessageEvent, WebSocket};
+
+/// A thin wrapper around the browser's WebSocket, usable from JS:
+///
+/// ```js
+/// let ws = wasm_bindgen.WsClient.connect(`ws://${location.host}/ws`);
+/// ws.on_message((text) => console.log(text));
+/// ws.send_text("Hello");
+/// ```
+#[wasm_bindgen]
+pub struct WsClient {
+    socket: WebSocket,
+    // Kept here so the callback lives as long as the client does
+    on_message: Option<Closure<dyn FnMut(MessageEvent)>>,
+}
+
+#[wasm_bindgen]
+impl WsClient {
+    pub fn connect(url: &str) -> Result<WsClient, JsValue> {
+        let socket = WebSocket::new(url)?;
+        Ok(Self {
+            socket,
+            on_message: None,
+        })
+    }
+
+    /// Sends a text frame. Fails if the socket isn't open yet.
+    pub fn send_text(&self, text: &str) -> Result<(), JsValue> {
+        self.socket.send_with_str(text)
+    }
+
+    /// Calls `callback` with the text of every message received.
+    /// Registering a new callback replaces the previous one.
+    pub fn on_message(&mut self, callback: js_sys::Function) {
+        let closure = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+            if let Some(text) = event.data().as_string() {
+                let _ = callback.call1(&JsValue::NULL, &JsValue::from_str(&text));
+            }
+        });
+        self.socket
+            .set_onmessage(Some(closure.as_ref().unchecked_ref()));
+        self.on_message = Some(closure);
+    }
+
+    pub fn on_open(&self, callback: js_sys::Function) {
+        self.socket.set_onopen(Some(&callback));
+    }
+
+    pub fn close(&self) -> Result<(), JsValue> {
+        self.socket.close()
+    }
+}
+
+impl Drop for WsClient {
+    fn drop(&mut self) {
+        // Unhook the handler before the closure it points to is freed
+        self.socket.set_onmessage(None);
+        let _ = self.socket.close();
+    }
+}
@@ -0,0 +1,4 @@
+#!/bin/bash
+# No wasm-bindgen step: the .wasm file is all the browser needs
+cargo build --release --target wasm32-unknown-unknown
+cp target/wasm32-unknown-unknown/release/wasm_raw.wasm ../wasm_web_server/web/
@@ -0,0 +1,104 @@
+//! The same kind of module as `wasm_lib`, without wasm-bindgen. Only
+//! numbers (i32, i64, f32, f64) cross the boundary between JavaScript and
+//! WASM, so strings and byte arrays travel as a pointer and a length into
+//! the module's linear memory. See `web/raw_wasm.js` in wasm_web_server for
+//! the other side.
+
+use std::alloc::Layout;
+
+// Functions the JavaScript loader provides in its `env` import object
+extern "C" {
+    fn log_number(n: i32);
+    fn log_str(ptr: *const u8, len: usize);
+}
+
+fn log(s: &str) {
+    unsafe { log_str(s.as_ptr(), s.len()) }
+}
+
+#[no_mangle]
+pub extern "C" fn hello_js() {
+    log("Hello from Rust, no wasm-bindgen required!");
+}
+
+#[no_mangle]
+pub extern "C" fn add(a: i32, b: i32) -> i32 {
+    // Overflow wraps, as it does in JavaScript's `(a + b) | 0`
+    let result = a.wrapping_add(b);
+    unsafe { log_number(result) };
+    result
+}
+
+/// Reserves `len` bytes of linear memory for JavaScript to write into.
+/// Hand the pointer back to `dealloc` when you're done with it.
+#[no_mangle]
+pub extern "C" fn alloc(len: usize) -> *mut u8 {
+    if len == 0 {
+        // The allocator can't hand out zero bytes, but nothing will be
+        // read or written through this pointer anyway
+        return std::ptr::NonNull::dangling().as_ptr();
+    }
+    let layout = Layout::array::<u8>(len).unwrap();
+    let ptr = unsafe { std::alloc::alloc(layout) };
+    if ptr.is_null() {
+        std::alloc::handle_alloc_error(layout);
+    }
+    ptr
+}
+
+/// # Safety
+///
+/// `ptr` and `len` must come from a single call to `alloc`.
+#[no_mangle]
+pub unsafe extern "C" fn dealloc(ptr: *mut u8, len: usize) {
+    // Freed with the same layout it was allocated with
+    if len != 0 {
+        std::alloc::dealloc(ptr, Layout::array::<u8>(len).unwrap());
+    }
+}
+
+/// Adds up a byte buffer that JavaScript copied into linear memory.
+///
+/// # Safety
+///
+/// `ptr` must point to `len` initialized bytes.
+#[no_mangle]
+pub unsafe extern "C" fn sum_bytes(ptr: *const u8, len: usize) -> u32 {
+    let bytes = std::slice::from_raw_parts(ptr, len);
+    bytes.iter().map(|b| *b as u32).sum()
+}
+
+/// Upper-cases ASCII text in place. JavaScript reads the result back out of
+/// the same buffer.
+///
+/// # Safety
+///
+/// `ptr` must point to `len` initialized bytes that nothing else is using.
+#[no_mangle]
+pub unsafe extern "C" fn to_uppercase(ptr: *mut u8, len: usize) {
+    let bytes = std::slice::from_raw_parts_mut(ptr, len);
+    bytes.make_ascii_uppercase();
+}
+
+/// Writes "Hello {name}" into `out` and returns how many bytes it needed.
+/// Like C's `snprintf`, if that's more than `out_len` the output is cut
+/// short and the caller can try again with a bigger buffer.
+///
+/// # Safety
+///
+/// `name` must point to `name_len` bytes of UTF-8, and `out` to `out_len`
+/// writable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn greet(
+    name: *const u8,
+    name_len: usize,
+    out: *mut u8,
+    out_len: usize,
+) -> usize {
+    let name = std::slice::from_raw_parts(name, name_len);
+    let greeting = [b"Hello ".as_slice(), name].concat();
+    let out = std::slice::from_raw_parts_mut(out, out_len);
+    let written = greeting.len().min(out_len);
+    out[..written].copy_from_slice(&greeting[..written]);
+    greeting.len()
+}
@@ -0,0 +1,118 @@
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::Path,
+    process::Command,
+};
+
+// Rebuilds wasm_lib and puts the wasm-bindgen output in pkg/ (ignored by
+// git), which the server checks before web/, so it always serves the current
+// bundle. Needs the wasm32-unknown-unknown target and a wasm-bindgen CLI that
+// matches wasm_lib's wasm-bindgen version
+// (`cargo install wasm-bindgen-cli --version 0.2.89`). If either is missing,
+// the bundle committed in web/ is served instead.
+//
+// Then every .wasm and .js file that will be served (pkg/'s, or web/'s when
+// pkg/ doesn't have one by that name) gets .br and .gz copies in pkg/, which
+// static_files serves to browsers that accept them. That part always runs.
+//
+// Set SKIP_WASM_BUILD=1 to skip rebuilding wasm_lib.
+fn main() {
+    let lib_dir = Path::new("../wasm_lib");
+    let pkg_dir = Path::new("pkg");
+    println!("cargo:rerun-if-changed=../wasm_lib/src");
+    println!("cargo:rerun-if-changed=../wasm_lib/Cargo.toml");
+    println!("cargo:rerun-if-changed=web");
+    println!("cargo:rerun-if-env-changed=SKIP_WASM_BUILD");
+
+    if env::var_os("SKIP_WASM_BUILD").is_none() {
+        if let Err(e) = build_wasm(lib_dir) {
+            println!("cargo:warning=Not rebuilding wasm_lib: {e}");
+            println!("cargo:warning=Serving the last bundle that was built.");
+        }
+    }
+
+    if let Err(e) = precompress(Path::new("web"), pkg_dir) {
+        println!("cargo:warning=Unable to precompress into pkg/: {e}");
+    }
+}
+
+fn precompress(web_dir: &Path, pkg_dir: &Path) -> io::Result<()> {
+    fs::create_dir_all(pkg_dir)?;
+    let mut sources = Vec::new();
+    for dir in [pkg_dir, web_dir] {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if !matches!(path.extension().and_then(|e| e.to_str()), Some("wasm" | "js")) {
+                continue;
+            }
+            // The server looks in pkg/ first, so a copy there wins
+            if dir == web_dir && pkg_dir.join(path.file_name().unwrap()).exists() {
+                continue;
+            }
+            sources.push(path);
+        }
+    }
+
+    for path in sources {
+        let modified = fs::metadata(&path)?.modified()?;
+        for suffix in ["br", "gz"] {
+            let mut name = path.file_name().unwrap().to_os_string();
+            name.push(format!(".{suffix}"));
+            let compressed = pkg_dir.join(name);
+            // Only redo the ones that are older than the file they came from
+            let up_to_date = fs::metadata(&compressed)
+                .and_then(|m| m.modified())
+                .is_ok_and(|compressed| compressed >= modified);
+            if up_to_date {
+                continue;
+            }
+
+            let contents = fs::read(&path)?;
+            let file = fs::File::create(&compressed)?;
+            if suffix == "br" {
+                // Quality 11, a 4MB window: slow, but it only happens at build time
+                let mut writer = brotli::CompressorWriter::new(file, 4096, 11, 22);
+                writer.write_all(&contents)?;
+                writer.flush()?;
+            } else {
+                let mut writer = flate2::write::GzEncoder::new(file, flate2::Compression::best());
+                writer.write_all(&contents)?;
+                writer.finish()?;
+            }
+        }
+    }
+    Ok(())
+}
+
+fn build_wasm(lib_dir: &Path) -> Result<(), String> {
+    // wasm_lib isn't in the workspace, so it has its own target directory
+    // and won't wait on the lock this build is holding.
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .current_dir(lib_dir)
+        .args(["build", "--release", "--target", "wasm32-unknown-unknown"])
+        // Don't pass on our own compiler flags (or clippy) to the wasm build
+        .env_remove("CARGO_ENCODED_RUSTFLAGS")
+        .env_remove("RUSTC_WORKSPACE_WRAPPER")
+        .env_remove("CARGO_TARGET_DIR")
+        .status()
+        .map_err(|e| format!("unable to run cargo: {e}"))?;
+    if !status.success() {
+        return Err("cargo build for wasm32-unknown-unknown failed".to_string());
+    }
+
+    let out_dir = Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("pkg");
+    let status = Command::new("wasm-bindgen")
+        .current_dir(lib_dir)
+        .arg("target/wasm32-unknown-unknown/release/wasm_lib.wasm")
+        .arg("--out-dir")
+        .arg(&out_dir)
+        .args(["--no-modules", "--no-typescript"])
+        .status()
+        .map_err(|e| format!("unable to run wasm-bindgen ({e}). Is wasm-bindgen-cli installed?"))?;
+    if !status.success() {
+        return Err("wasm-bindgen failed".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,187 @@
+use auth_core::{login_with_store, JsonFileStore, LoginAction};
+use axum::{
+    extract::ConnectInfo,
+    http::{header, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use std::{
+    future::Future,
+    net::SocketAddr,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+
+/// Requires HTTP Basic authentication, checked with the login library.
+/// Requests that log in successfully have their `LoginRole` added as a
+/// request extension, so handlers can take `Extension<LoginRole>`.
+#[derive(Clone, Default)]
+pub struct AuthLayer {
+    store: Arc<JsonFileStore>,
+}
+
+impl AuthLayer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner,
+            store: self.store.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    store: Arc<JsonFileStore>,
+}
+
+fn unauthorized() -> Response {
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Basic realm=\"wasm_web_server\"")],
+        "Unauthorized",
+    )
+        .into_response()
+}
+
+/// Pulls the username and password out of an `Authorization: Basic ...` header.
+fn parse_basic_auth<B>(req: &Request<B>) -> Option<(String, String)> {
+    let encoded = req
+        .headers()
+        .get(header::AUTHORIZATION)?
+        .to_str()
+        .ok()?
+        .strip_prefix("Basic ")?;
+    let decoded = String::from_utf8(STANDARD.decode(encoded).ok()?).ok()?;
+    let (username, password) = decoded.split_once(':')?;
+    Some((username.to_string(), password.to_string()))
+}
+
+impl<S, B> Service<Request<B>> for AuthService<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        // The clone might not be ready, so keep the service that
+        // poll_ready was called on and leave the clone in its place.
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let store = self.store.clone();
+
+        Box::pin(async move {
+            let Some((username, password)) = parse_basic_auth(&req) else {
+                return Ok(unauthorized());
+            };
+            let source = req
+                .extensions()
+                .get::<ConnectInfo<SocketAddr>>()
+                .map(|ConnectInfo(addr)| addr.ip().to_string())
+                .unwrap_or_else(|| "unknown".to_string());
+
+            let result = tokio::task::spawn_blocking(move || {
+                login_with_store(&*store, &username, &password, &source)
+            })
+            .await;
+
+            match result {
+                Ok(Ok(LoginAction::Granted(role))) => {
+                    req.extensions_mut().insert(role);
+                    inner.call(req).await
+                }
+                Ok(Ok(LoginAction::Denied)) => Ok(unauthorized()),
+                Ok(Err(e)) => {
+                    tracing::error!("Login error: {e}");
+                    Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response())
+                }
+                Err(_) => Ok(StatusCode::INTERNAL_SERVER_ERROR.into_response()),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use auth_core::{LoginRole, User, UserStore};
+    use axum::{body::Body, routing::get, Extension, Router};
+    use tower::ServiceExt;
+
+    async fn role(Extension(role): Extension<LoginRole>) -> String {
+        role.to_string()
+    }
+
+    // bob's password is Passw0rd. Logins are audited in the temp directory.
+    fn app() -> Router {
+        let path = std::env::temp_dir().join(format!("wasm_web_server_users_{}.json", std::process::id()));
+        static ONCE: std::sync::Once = std::sync::Once::new();
+        ONCE.call_once(|| {
+            let name = format!("wasm_web_server_audit_{}.jsonl", std::process::id());
+            std::env::set_var("AUDIT_LOG", std::env::temp_dir().join(name));
+            let bob = User::new("bob", "Passw0rd", LoginRole::User);
+            let users = [(bob.username.clone(), bob)].into_iter().collect();
+            JsonFileStore::new(&path).save_users(&users).unwrap();
+        });
+        let layer = AuthLayer {
+            store: Arc::new(JsonFileStore::new(path)),
+        };
+        Router::new().route("/", get(role)).layer(layer)
+    }
+
+    async fn call(authorization: Option<&str>) -> Response {
+        let mut request = Request::builder().uri("/");
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        app().oneshot(request.body(Body::empty()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejected_logins_ask_for_basic_auth() {
+        let wrong_password = format!("Basic {}", STANDARD.encode("bob:wrong"));
+        let no_colon = format!("Basic {}", STANDARD.encode("bob"));
+        for authorization in [
+            None,
+            Some("Bearer some.jwt.token"),
+            Some("Basic not base64!"),
+            Some(no_colon.as_str()),
+            Some(wrong_password.as_str()),
+        ] {
+            let response = call(authorization).await;
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{authorization:?}");
+            assert_eq!(
+                response.headers()[header::WWW_AUTHENTICATE],
+                "Basic realm=\"wasm_web_server\""
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_good_login_reaches_the_handler() {
+        let authorization = format!("Basic {}", STANDARD.encode("bob:Passw0rd"));
+        let response = call(Some(&authorization)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get(header::WWW_AUTHENTICATE).is_none());
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], LoginRole::User.to_string().as_bytes());
+    }
+}
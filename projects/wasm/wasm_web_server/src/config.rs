@@ -0,0 +1,64 @@
+use figment::{
+    providers::{Env, Format, Serialized, Toml},
+    Figment,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    path::PathBuf,
+};
+
+// Environment variables that override the matching setting
+const ENV_KEYS: [&str; 5] = ["bind_addr", "port", "log_level", "static_dir", "build_dir"];
+
+/// Where the server listens and what it serves. Each layer overrides the
+/// one before: built-in defaults, then `settings.toml` (or the file named by
+/// SETTINGS_FILE) if it exists, then environment variables named after the
+/// fields in capitals (BIND_ADDR, PORT, LOG_LEVEL, STATIC_DIR, BUILD_DIR).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Settings {
+    pub bind_addr: IpAddr,
+    pub port: u16,
+    /// Used when RUST_LOG isn't set
+    pub log_level: String,
+    /// Serve files from here for any path without a route
+    pub static_dir: PathBuf,
+    /// Where build.rs puts the wasm_lib bundle. Files here win over static_dir.
+    pub build_dir: PathBuf,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            bind_addr: IpAddr::V4(Ipv4Addr::LOCALHOST),
+            port: 3001,
+            log_level: "info".to_string(),
+            static_dir: PathBuf::from("web"),
+            build_dir: PathBuf::from("pkg"),
+        }
+    }
+}
+
+fn is_set(name: &str) -> bool {
+    std::env::var(name).is_ok_and(|value| !value.trim().is_empty())
+}
+
+impl Settings {
+    pub fn load() -> Result<Self, Box<figment::Error>> {
+        let file = std::env::var("SETTINGS_FILE").unwrap_or_else(|_| "settings.toml".to_string());
+        let settings: Settings = Figment::from(Serialized::defaults(Settings::default()))
+            .merge(Toml::file(file))
+            // Blank variables count as unset
+            .merge(Env::raw().only(&ENV_KEYS).filter(|key| is_set(key.as_str())))
+            .extract()
+            .map_err(Box::new)?;
+        if settings.port == 0 {
+            return Err(Box::new("port must be from 1 to 65535".to_string().into()));
+        }
+        Ok(settings)
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        SocketAddr::new(self.bind_addr, self.port)
+    }
+}
@@ -0,0 +1,31 @@
+use crate::shutdown::ShutdownReceiver;
+use axum::{
+    response::sse::{Event, KeepAlive, Sse},
+    Extension,
+};
+use futures::stream::{self, Stream};
+use std::{convert::Infallible, time::Duration};
+
+/// Streams a `counter` event every second. Keep-alive comments go out in
+/// between so proxies don't drop the idle connection.
+pub async fn sse_handler(
+    Extension(shutdown): Extension<ShutdownReceiver>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let interval = tokio::time::interval(Duration::from_secs(1));
+
+    let stream = stream::unfold(
+        (0u64, interval, shutdown),
+        |(count, mut interval, mut shutdown)| async move {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let event = Event::default().event("counter").data(count.to_string());
+                    Some((Ok(event), (count + 1, interval, shutdown)))
+                }
+                // Ending the stream lets graceful shutdown finish the response
+                _ = shutdown.changed() => None,
+            }
+        },
+    );
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
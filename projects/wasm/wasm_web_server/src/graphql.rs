@@ -0,0 +1,84 @@
+use crate::{
+    people::{insert_person, People, EMPTY_NAME},
+    person::Person,
+};
+use async_graphql::{http::GraphiQLSource, Context, EmptySubscription, Object, Schema, SimpleObject};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::{response::Html, Extension};
+
+pub type PeopleSchema = Schema<Query, Mutation, EmptySubscription>;
+
+/// Builds the schema around the same store the REST routes use.
+pub fn schema(people: People) -> PeopleSchema {
+    Schema::build(Query, Mutation, EmptySubscription)
+        .data(people)
+        .finish()
+}
+
+#[derive(SimpleObject)]
+struct PersonObject {
+    id: u64,
+    name: String,
+    age: u8,
+}
+
+impl PersonObject {
+    fn new(id: u64, person: &Person) -> Self {
+        Self {
+            id,
+            name: person.name.clone(),
+            age: person.age,
+        }
+    }
+}
+
+pub struct Query;
+
+#[Object]
+impl Query {
+    /// Everyone, ordered by id
+    async fn people(&self, ctx: &Context<'_>) -> Vec<PersonObject> {
+        let people = ctx.data_unchecked::<People>().read().unwrap();
+        let mut list: Vec<PersonObject> = people
+            .iter()
+            .map(|(id, person)| PersonObject::new(*id, person))
+            .collect();
+        list.sort_by_key(|p| p.id);
+        list
+    }
+
+    async fn person(&self, ctx: &Context<'_>, id: u64) -> Option<PersonObject> {
+        let people = ctx.data_unchecked::<People>().read().unwrap();
+        people.get(&id).map(|person| PersonObject::new(id, person))
+    }
+}
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    async fn add_person(
+        &self,
+        ctx: &Context<'_>,
+        name: String,
+        age: u8,
+    ) -> async_graphql::Result<PersonObject> {
+        if name.trim().is_empty() {
+            return Err(EMPTY_NAME.into());
+        }
+        let person = Person { name, age };
+        let id = insert_person(ctx.data_unchecked::<People>(), person.clone());
+        Ok(PersonObject::new(id, &person))
+    }
+}
+
+pub async fn graphql_handler(
+    Extension(schema): Extension<PeopleSchema>,
+    request: GraphQLRequest,
+) -> GraphQLResponse {
+    schema.execute(request.into_inner()).await.into()
+}
+
+pub async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
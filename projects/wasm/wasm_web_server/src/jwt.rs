@@ -0,0 +1,214 @@
+use auth_core::{login_with_source, LoginAction, LoginRole};
+use axum::{
+    async_trait,
+    extract::{ConnectInfo, FromRequestParts},
+    http::{header, request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::{
+    net::SocketAddr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const TOKEN_LIFETIME_SECS: u64 = 60 * 60;
+
+struct Keys {
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+}
+
+static KEYS: Lazy<Keys> = Lazy::new(|| {
+    let secret = std::env::var("JWT_SECRET").unwrap_or_else(|_| {
+        tracing::warn!("JWT_SECRET is not set, using an insecure development secret");
+        "insecure-development-secret".to_string()
+    });
+    Keys {
+        encoding: EncodingKey::from_secret(secret.as_bytes()),
+        decoding: DecodingKey::from_secret(secret.as_bytes()),
+    }
+});
+
+/// The contents of a token. Handlers that take `Claims` as an argument
+/// are only called when the request carries a valid token.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: String,
+    pub role: LoginRole,
+    pub exp: u64,
+}
+
+#[derive(Debug)]
+pub enum AuthError {
+    WrongCredentials,
+    MissingToken,
+    InvalidToken,
+    TokenCreation,
+    Internal,
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+    error: &'static str,
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let (status, error) = match self {
+            AuthError::WrongCredentials => (StatusCode::UNAUTHORIZED, "Invalid username or password"),
+            AuthError::MissingToken => (StatusCode::UNAUTHORIZED, "Missing bearer token"),
+            AuthError::InvalidToken => (StatusCode::UNAUTHORIZED, "Invalid or expired token"),
+            AuthError::TokenCreation => (StatusCode::INTERNAL_SERVER_ERROR, "Unable to create token"),
+            AuthError::Internal => (StatusCode::INTERNAL_SERVER_ERROR, "Unable to check credentials"),
+        };
+        (status, Json(ErrorJson { error })).into_response()
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for Claims
+where
+    S: Send + Sync,
+{
+    type Rejection = AuthError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .ok_or(AuthError::MissingToken)?;
+        let data = decode::<Claims>(token, &KEYS.decoding, &Validation::default())
+            .map_err(|_| AuthError::InvalidToken)?;
+        Ok(data.claims)
+    }
+}
+
+#[derive(Deserialize)]
+pub struct Credentials {
+    username: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+pub struct TokenResponse {
+    access_token: String,
+    token_type: &'static str,
+    expires_in: u64,
+}
+
+/// Exchanges a username and password for a signed token.
+pub async fn issue_token(
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+    Json(credentials): Json<Credentials>,
+) -> Result<Json<TokenResponse>, AuthError> {
+    let username = credentials.username.clone();
+    let source = addr.ip().to_string();
+    let result = tokio::task::spawn_blocking(move || {
+        login_with_source(&credentials.username, &credentials.password, &source)
+    })
+    .await
+    .map_err(|_| AuthError::Internal)?;
+
+    let role = match result {
+        Ok(LoginAction::Granted(role)) => role,
+        Ok(LoginAction::Denied) => return Err(AuthError::WrongCredentials),
+        Err(e) => {
+            tracing::error!("Login error: {e}");
+            return Err(AuthError::Internal);
+        }
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| AuthError::TokenCreation)?
+        .as_secs();
+    let claims = Claims {
+        sub: username,
+        role,
+        exp: now + TOKEN_LIFETIME_SECS,
+    };
+    let access_token = encode(&Header::default(), &claims, &KEYS.encoding)
+        .map_err(|_| AuthError::TokenCreation)?;
+
+    Ok(Json(TokenResponse {
+        access_token,
+        token_type: "Bearer",
+        expires_in: TOKEN_LIFETIME_SECS,
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{body::Body, http::Request, routing::get, Router};
+    use tower::ServiceExt;
+
+    async fn whoami(claims: Claims) -> String {
+        claims.sub
+    }
+
+    fn now() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs()
+    }
+
+    fn token(exp: u64, key: &EncodingKey) -> String {
+        let claims = Claims {
+            sub: "bob".to_string(),
+            role: LoginRole::User,
+            exp,
+        };
+        encode(&Header::default(), &claims, key).unwrap()
+    }
+
+    async fn call(authorization: Option<&str>) -> Response {
+        let mut request = Request::builder().uri("/");
+        if let Some(authorization) = authorization {
+            request = request.header(header::AUTHORIZATION, authorization);
+        }
+        let app = Router::new().route("/", get(whoami));
+        app.oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn status_for(authorization: Option<&str>) -> StatusCode {
+        call(authorization).await.status()
+    }
+
+    #[tokio::test]
+    async fn test_valid_token() {
+        let token = token(now() + 60, &KEYS.encoding);
+        let response = call(Some(&format!("Bearer {token}"))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(&body[..], b"bob");
+    }
+
+    #[tokio::test]
+    async fn test_rejected_tokens() {
+        assert_eq!(status_for(None).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status_for(Some("Basic Ym9iOnBhc3N3b3Jk")).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(status_for(Some("Bearer not.a.token")).await, StatusCode::UNAUTHORIZED);
+
+        // Well past the default 60 seconds of leeway
+        let expired = token(now() - 3600, &KEYS.encoding);
+        assert_eq!(status_for(Some(&format!("Bearer {expired}"))).await, StatusCode::UNAUTHORIZED);
+
+        let forged = token(now() + 60, &EncodingKey::from_secret(b"someone else's secret"));
+        assert_eq!(status_for(Some(&format!("Bearer {forged}"))).await, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_rejection_is_json() {
+        let response = AuthError::InvalidToken.into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(json["error"], "Invalid or expired token");
+    }
+}
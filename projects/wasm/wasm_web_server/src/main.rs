@@ -1,8 +1,34 @@
-use axum::{Router, routing::get};
-use std::net::SocketAddr;
-use tower_http::services::ServeDir;
+use auth_core::LoginRole;
+use anyhow::Context;
+use axum::{Extension, Router, routing::{get, post}};
+use std::{net::SocketAddr, time::Duration};
+use tower::ServiceBuilder;
+use tower_http::{
+    compression::{
+        predicate::{DefaultPredicate, NotForContentType, Predicate},
+        CompressionLayer,
+    },
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, SetRequestIdLayer},
+    trace::{DefaultOnResponse, TraceLayer},
+    LatencyUnit,
+};
+use tokio::sync::watch;
+use tracing::Level;
 use serde::Serialize;
 
+mod basic_auth;
+mod config;
+mod events;
+#[cfg(feature = "graphql")]
+mod graphql;
+mod jwt;
+mod pages;
+mod people;
+mod person;
+mod shutdown;
+mod static_files;
+mod websocket;
+
 #[derive(Serialize)]
 struct HelloJson {
     message: String,
@@ -14,14 +40,138 @@ async fn say_hello_json() -> axum::Json<HelloJson> {
     })
 }
 
+async fn protected(claims: jwt::Claims) -> axum::Json<HelloJson> {
+    axum::Json(HelloJson {
+        message: format!("Hello, {}. Your role is {}.", claims.sub, claims.role),
+    })
+}
+
+async fn basic_protected(Extension(role): Extension<LoginRole>) -> axum::Json<HelloJson> {
+    axum::Json(HelloJson {
+        message: format!("Hello! You logged in with Basic auth as {role}."),
+    })
+}
+
 #[tokio::main]
-async fn main() {
+async fn main() -> anyhow::Result<()> {
+    let settings = config::Settings::load()?;
+    telemetry::init(&settings.log_level);
+    let tls = web_tls::TlsSettings::from_env()?;
+    let (shutdown_tx, shutdown_rx) = shutdown::channel();
+    let people = people::new_people();
+
     let app = Router::new()
         .route("/json", get(say_hello_json))
-        .fallback_service(ServeDir::new("web"));
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3001));    
-    axum::Server::bind(&addr)
-        .serve(app.into_make_service())
-        .await
-        .unwrap();
+        .route("/token", post(jwt::issue_token))
+        .route("/protected", get(protected))
+        .route("/basic", get(basic_protected).layer(basic_auth::AuthLayer::new()))
+        .route("/ws", get(websocket::ws_handler))
+        .route("/events", get(events::sse_handler))
+        .route("/person_bin", post(person::person_bin))
+        .route("/people", get(people::list_people).post(people::create_person))
+        .route("/people/html", get(pages::people_html))
+        .route(
+            "/people/:id",
+            get(people::get_person)
+                .put(people::update_person)
+                .delete(people::delete_person),
+        )
+        .fallback_service(static_files::service(
+            &settings.build_dir,
+            &settings.static_dir,
+        ));
+
+    #[cfg(feature = "graphql")]
+    let app = app
+        .route(
+            "/graphql",
+            get(graphql::graphiql).post(graphql::graphql_handler),
+        )
+        .layer(Extension(graphql::schema(people.clone())));
+
+    let app = app
+        .layer(Extension(websocket::broadcaster()))
+        .layer(Extension(people))
+        .layer(Extension(shutdown_rx))
+        .layer(axum::middleware::from_fn(static_files::etag))
+        // A compressor buffers output, which would hold back each SSE event
+        .layer(CompressionLayer::new().compress_when(
+            DefaultPredicate::new().and(NotForContentType::const_new("text/event-stream")),
+        ))
+        .layer(
+            ServiceBuilder::new()
+                .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
+                .layer(
+                    TraceLayer::new_for_http()
+                        .make_span_with(telemetry::make_span)
+                        .on_response(
+                            DefaultOnResponse::new()
+                                .level(Level::INFO)
+                                .latency_unit(LatencyUnit::Millis),
+                        ),
+                )
+                .layer(PropagateRequestIdLayer::x_request_id()),
+        );
+    let addr = settings.addr();
+    match tls {
+        Some(tls) => serve_https(app, addr, tls, &shutdown_tx).await?,
+        None => serve_http(app, addr, &shutdown_tx).await?,
+    }
+
+    // In-flight requests are done; give WebSocket sessions a moment to say goodbye
+    shutdown::wait_for_tasks(&shutdown_tx, Duration::from_secs(5)).await;
+    Ok(())
+}
+
+async fn serve_http(
+    app: Router,
+    addr: SocketAddr,
+    shutdown_tx: &watch::Sender<bool>,
+) -> anyhow::Result<()> {
+    tracing::info!("Listening on http://{addr}");
+    axum::Server::try_bind(&addr)
+        .with_context(|| format!("Unable to listen on {addr}"))?
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+        .with_graceful_shutdown(shutdown::signal(shutdown_tx))
+        .await?;
+    Ok(())
+}
+
+async fn serve_https(
+    app: Router,
+    addr: SocketAddr,
+    tls: web_tls::TlsSettings,
+    shutdown_tx: &watch::Sender<bool>,
+) -> anyhow::Result<()> {
+    let config = tls.rustls_config().await?;
+    if tls.redirect_http {
+        let mut shutdown = shutdown_tx.subscribe();
+        let redirect = web_tls::redirect_server(addr, tls.port, async move {
+            let _ = shutdown.changed().await;
+        })?;
+        tracing::info!("Redirecting http://{addr} to HTTPS");
+        tokio::spawn(async move {
+            if let Err(e) = redirect.await {
+                tracing::error!("HTTP redirect server failed: {e}");
+            }
+        });
+    }
+
+    let https_addr = SocketAddr::new(addr.ip(), tls.port);
+    tracing::info!("Listening on https://{https_addr}");
+    let handle = axum_server::Handle::new();
+    let server = axum_server::bind_rustls(https_addr, config)
+        .handle(handle.clone())
+        .serve(app.into_make_service_with_connect_info::<SocketAddr>());
+    tokio::pin!(server);
+
+    let result = tokio::select! {
+        result = &mut server => result,
+        _ = shutdown::signal(shutdown_tx) => {
+            // Let in-flight requests finish, as hyper's graceful shutdown does
+            handle.graceful_shutdown(Some(Duration::from_secs(10)));
+            server.await
+        }
+    };
+    result.with_context(|| format!("Unable to serve HTTPS on {https_addr}"))
 }
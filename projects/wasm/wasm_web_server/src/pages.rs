@@ -0,0 +1,51 @@
+use crate::{people::People, person::Person};
+use askama::Template;
+use axum::{
+    http::StatusCode,
+    response::{Html, IntoResponse, Response},
+    Extension,
+};
+
+#[derive(Template)]
+#[template(path = "people.html")]
+struct PeopleTemplate {
+    people: Vec<(u64, Person)>,
+}
+
+#[derive(Template)]
+#[template(path = "error.html")]
+struct ErrorTemplate {
+    status: StatusCode,
+    message: String,
+}
+
+/// Renders a template, or the error page if that fails.
+fn render(template: impl Template) -> Response {
+    match template.render() {
+        Ok(html) => Html(html).into_response(),
+        Err(e) => error_page(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+    }
+}
+
+fn error_page(status: StatusCode, message: &str) -> Response {
+    let page = ErrorTemplate {
+        status,
+        message: message.to_string(),
+    };
+    match page.render() {
+        Ok(html) => (status, Html(html)).into_response(),
+        // Nothing left to fall back on
+        Err(_) => status.into_response(),
+    }
+}
+
+pub async fn people_html(Extension(people): Extension<People>) -> Response {
+    let mut people: Vec<(u64, Person)> = people
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(id, person)| (*id, person.clone()))
+        .collect();
+    people.sort_by_key(|(id, _)| *id);
+    render(PeopleTemplate { people })
+}
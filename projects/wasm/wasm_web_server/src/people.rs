@@ -0,0 +1,488 @@
+use crate::person::Person;
+use axum::{
+    extract::{
+        rejection::{JsonRejection, PathRejection, QueryRejection},
+        Path, Query,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock,
+    },
+};
+
+// Person id -> person
+pub type People = Arc<RwLock<HashMap<u64, Person>>>;
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+pub fn new_people() -> People {
+    Arc::new(RwLock::new(HashMap::new()))
+}
+
+/// Stores a new person and returns their id.
+pub fn insert_person(people: &People, person: Person) -> u64 {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    people.write().unwrap().insert(id, person);
+    id
+}
+
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+    details: Vec<FieldError>,
+}
+
+/// Says which parameter was wrong, so clients can point at it.
+#[derive(Serialize)]
+pub struct FieldError {
+    field: &'static str,
+    message: String,
+}
+
+impl ApiError {
+    fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            details: Vec::new(),
+        }
+    }
+
+    fn not_found(id: u64) -> Self {
+        Self::new(StatusCode::NOT_FOUND, format!("No person with id {id}"))
+    }
+}
+
+#[derive(Serialize)]
+struct ErrorJson {
+    error: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    details: Vec<FieldError>,
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let body = ErrorJson {
+            error: self.message,
+            details: self.details,
+        };
+        (self.status, Json(body)).into_response()
+    }
+}
+
+// Axum's own rejections are plain text; keep errors JSON like everything else
+impl From<JsonRejection> for ApiError {
+    fn from(rejection: JsonRejection) -> Self {
+        Self::new(rejection.status(), rejection.body_text())
+    }
+}
+
+impl From<PathRejection> for ApiError {
+    fn from(rejection: PathRejection) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, rejection.body_text())
+    }
+}
+
+#[derive(Serialize)]
+pub struct PersonWithId {
+    id: u64,
+    #[serde(flatten)]
+    person: Person,
+}
+
+pub const EMPTY_NAME: &str = "name must not be empty";
+
+fn validate(person: &Person) -> Result<(), ApiError> {
+    if person.name.trim().is_empty() {
+        return Err(ApiError::new(StatusCode::UNPROCESSABLE_ENTITY, EMPTY_NAME));
+    }
+    Ok(())
+}
+
+#[derive(Deserialize, Clone, Copy, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SortBy {
+    #[default]
+    Id,
+    Name,
+    Age,
+}
+
+/// `GET /people?page=2&per_page=10&sort=age`. Every parameter is optional.
+#[derive(Deserialize)]
+pub struct ListParams {
+    #[serde(default = "ListParams::default_page")]
+    page: usize,
+    #[serde(default = "ListParams::default_per_page")]
+    per_page: usize,
+    #[serde(default)]
+    sort: SortBy,
+}
+
+impl ListParams {
+    const MAX_PER_PAGE: usize = 100;
+
+    fn default_page() -> usize {
+        1
+    }
+
+    fn default_per_page() -> usize {
+        10
+    }
+
+    fn validate(&self) -> Result<(), ApiError> {
+        let mut details = Vec::new();
+        if self.page == 0 {
+            details.push(FieldError {
+                field: "page",
+                message: "must be at least 1".to_string(),
+            });
+        }
+        if self.per_page == 0 || self.per_page > Self::MAX_PER_PAGE {
+            details.push(FieldError {
+                field: "per_page",
+                message: format!("must be between 1 and {}", Self::MAX_PER_PAGE),
+            });
+        }
+        if details.is_empty() {
+            Ok(())
+        } else {
+            let mut error = ApiError::new(StatusCode::BAD_REQUEST, "Invalid query parameters");
+            error.details = details;
+            Err(error)
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct PeoplePage {
+    people: Vec<PersonWithId>,
+    total: usize,
+    page: usize,
+    per_page: usize,
+    next_page: Option<usize>,
+}
+
+pub async fn list_people(
+    Extension(people): Extension<People>,
+    params: Result<Query<ListParams>, QueryRejection>,
+) -> Result<Json<PeoplePage>, ApiError> {
+    let Query(params) = params?;
+    params.validate()?;
+
+    let mut list: Vec<PersonWithId> = people
+        .read()
+        .unwrap()
+        .iter()
+        .map(|(id, person)| PersonWithId {
+            id: *id,
+            person: person.clone(),
+        })
+        .collect();
+    match params.sort {
+        SortBy::Id => list.sort_by_key(|p| p.id),
+        SortBy::Name => list.sort_by(|a, b| a.person.name.cmp(&b.person.name)),
+        SortBy::Age => list.sort_by_key(|p| p.person.age),
+    }
+
+    let total = list.len();
+    let start = (params.page - 1).saturating_mul(params.per_page);
+    let people: Vec<PersonWithId> = list
+        .into_iter()
+        .skip(start)
+        .take(params.per_page)
+        .collect();
+    let next_page = if start.saturating_add(params.per_page) < total {
+        Some(params.page + 1)
+    } else {
+        None
+    };
+
+    Ok(Json(PeoplePage {
+        people,
+        total,
+        page: params.page,
+        per_page: params.per_page,
+        next_page,
+    }))
+}
+
+pub async fn get_person(
+    id: Result<Path<u64>, PathRejection>,
+    Extension(people): Extension<People>,
+) -> Result<Json<PersonWithId>, ApiError> {
+    let Path(id) = id?;
+    let people = people.read().unwrap();
+    let person = people.get(&id).ok_or(ApiError::not_found(id))?;
+    Ok(Json(PersonWithId {
+        id,
+        person: person.clone(),
+    }))
+}
+
+pub async fn create_person(
+    Extension(people): Extension<People>,
+    body: Result<Json<Person>, JsonRejection>,
+) -> Result<(StatusCode, Json<PersonWithId>), ApiError> {
+    let Json(person) = body?;
+    validate(&person)?;
+
+    let id = insert_person(&people, person.clone());
+    Ok((StatusCode::CREATED, Json(PersonWithId { id, person })))
+}
+
+pub async fn update_person(
+    id: Result<Path<u64>, PathRejection>,
+    Extension(people): Extension<People>,
+    body: Result<Json<Person>, JsonRejection>,
+) -> Result<Json<PersonWithId>, ApiError> {
+    let Path(id) = id?;
+    let Json(person) = body?;
+    validate(&person)?;
+
+    let mut people = people.write().unwrap();
+    let existing = people.get_mut(&id).ok_or(ApiError::not_found(id))?;
+    *existing = person.clone();
+    Ok(Json(PersonWithId { id, person }))
+}
+
+pub async fn delete_person(
+    id: Result<Path<u64>, PathRejection>,
+    Extension(people): Extension<People>,
+) -> Result<StatusCode, ApiError> {
+    let Path(id) = id?;
+    people
+        .write()
+        .unwrap()
+        .remove(&id)
+        .ok_or(ApiError::not_found(id))?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use axum::{
+        body::Body,
+        http::{header, Method, Request},
+        routing::get,
+        Router,
+    };
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/people", get(list_people).post(create_person))
+            .route(
+                "/people/:id",
+                get(get_person).put(update_person).delete(delete_person),
+            )
+            .layer(Extension(new_people()))
+    }
+
+    async fn send(
+        app: &Router,
+        method: Method,
+        uri: &str,
+        body: Option<&str>,
+    ) -> (StatusCode, Value) {
+        let request = Request::builder().method(method).uri(uri);
+        let request = match body {
+            Some(body) => request
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from(body.to_string())),
+            None => request.body(Body::empty()),
+        };
+        let response = app.clone().oneshot(request.unwrap()).await.unwrap();
+        let status = response.status();
+        let bytes = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        (status, serde_json::from_slice(&bytes).unwrap_or(Value::Null))
+    }
+
+    async fn create(app: &Router, name: &str, age: u8) -> u64 {
+        let body = json!({"name": name, "age": age}).to_string();
+        let (status, person) = send(app, Method::POST, "/people", Some(&body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        person["id"].as_u64().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_and_read() {
+        let app = app();
+        let body = r#"{"name": "Ada", "age": 36}"#;
+        let (status, created) = send(&app, Method::POST, "/people", Some(body)).await;
+        assert_eq!(status, StatusCode::CREATED);
+        assert_eq!(created["name"], "Ada");
+        assert_eq!(created["age"], 36);
+
+        let uri = format!("/people/{}", created["id"]);
+        let (status, read) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(read, created);
+    }
+
+    #[tokio::test]
+    async fn test_create_rejects_bad_bodies() {
+        let app = app();
+        let body = r#"{"name": " ", "age": 1}"#;
+        let (status, error) = send(&app, Method::POST, "/people", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error["error"], EMPTY_NAME);
+
+        // Not JSON at all, and JSON of the wrong shape
+        let (status, error) = send(&app, Method::POST, "/people", Some("{")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].is_string());
+        let body = r#"{"name": "Ada", "age": "old"}"#;
+        let (status, error) = send(&app, Method::POST, "/people", Some(body)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(error["error"].is_string());
+
+        // None of them were stored
+        let (_, list) = send(&app, Method::GET, "/people", None).await;
+        assert_eq!(list["total"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_read_missing_or_bad_id() {
+        let app = app();
+        let (status, error) = send(&app, Method::GET, "/people/999999", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error["error"], "No person with id 999999");
+
+        let (status, error) = send(&app, Method::GET, "/people/ada", None).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(error["error"].is_string());
+    }
+
+    #[tokio::test]
+    async fn test_update() {
+        let app = app();
+        let id = create(&app, "Ada", 36).await;
+        let uri = format!("/people/{id}");
+
+        let body = r#"{"name": "Ada Lovelace", "age": 37}"#;
+        let (status, updated) = send(&app, Method::PUT, &uri, Some(body)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated, json!({"id": id, "name": "Ada Lovelace", "age": 37}));
+        let (_, read) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(read, updated);
+
+        let (status, error) = send(&app, Method::PUT, &uri, Some(r#"{"name": "", "age": 37}"#)).await;
+        assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(error["error"], EMPTY_NAME);
+
+        let body = r#"{"name": "Nobody", "age": 1}"#;
+        let (status, error) = send(&app, Method::PUT, "/people/999999", Some(body)).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error["error"], "No person with id 999999");
+    }
+
+    #[tokio::test]
+    async fn test_delete() {
+        let app = app();
+        let id = create(&app, "Ada", 36).await;
+        let uri = format!("/people/{id}");
+
+        let (status, _) = send(&app, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (status, _) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, error) = send(&app, Method::DELETE, &uri, None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(error["error"], format!("No person with id {id}"));
+    }
+
+    fn fields(error: &Value) -> Vec<&str> {
+        error["details"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|detail| detail["field"].as_str().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_pages() {
+        let app = app();
+        for (name, age) in [("Cy", 30), ("Al", 50), ("Bo", 40)] {
+            create(&app, name, age).await;
+        }
+
+        let (status, page) = send(&app, Method::GET, "/people?per_page=2&sort=name", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["people"][0]["name"], "Al");
+        assert_eq!(page["people"][1]["name"], "Bo");
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["page"], 1);
+        assert_eq!(page["next_page"], 2);
+
+        let (_, page) = send(&app, Method::GET, "/people?page=2&per_page=2&sort=age", None).await;
+        assert_eq!(page["people"].as_array().unwrap().len(), 1);
+        assert_eq!(page["people"][0]["name"], "Al");
+        assert_eq!(page["next_page"], Value::Null);
+
+        // Past the end is an empty page, not an error
+        let (status, page) = send(&app, Method::GET, "/people?page=5&per_page=2", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["people"], json!([]));
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["next_page"], Value::Null);
+
+        // So far past that page * per_page overflows
+        let uri = format!("/people?page={}&per_page=100", usize::MAX);
+        let (status, page) = send(&app, Method::GET, &uri, None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["people"], json!([]));
+        assert_eq!(page["next_page"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn test_page_defaults_and_limits() {
+        let app = app();
+        let (_, page) = send(&app, Method::GET, "/people", None).await;
+        assert_eq!(page["page"], 1);
+        assert_eq!(page["per_page"], 10);
+
+        let (status, page) = send(&app, Method::GET, "/people?per_page=100", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["per_page"], 100);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_range_pages_are_rejected() {
+        let app = app();
+        for (query, expected) in [
+            ("page=0", vec!["page"]),
+            ("per_page=0", vec!["per_page"]),
+            ("per_page=101", vec!["per_page"]),
+            ("page=0&per_page=1000", vec!["page", "per_page"]),
+        ] {
+            let (status, error) = send(&app, Method::GET, &format!("/people?{query}"), None).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+            assert_eq!(error["error"], "Invalid query parameters");
+            assert_eq!(fields(&error), expected, "{query}");
+        }
+
+        // Values that don't parse never reach validation
+        for query in ["page=-1", "per_page=ten", "sort=height"] {
+            let (status, error) = send(&app, Method::GET, &format!("/people?{query}"), None).await;
+            assert_eq!(status, StatusCode::BAD_REQUEST, "{query}");
+            assert!(error["error"].is_string(), "{query}");
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use axum::{
+    body::Bytes,
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+
+// Matches the `Person` type in wasm_lib, so the two can exchange bincode
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Person {
+    pub name: String,
+    pub age: u8,
+}
+
+/// Accepts a bincode-encoded `Person` and sends it back a year older.
+pub async fn person_bin(headers: HeaderMap, body: Bytes) -> impl IntoResponse {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok());
+    if content_type != Some("application/octet-stream") {
+        return Err((
+            StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            "Expected application/octet-stream",
+        ));
+    }
+
+    let mut person: Person = bincode::deserialize(&body)
+        .map_err(|_| (StatusCode::BAD_REQUEST, "Body is not a bincode Person"))?;
+    person.age = person.age.saturating_add(1);
+
+    let bytes = bincode::serialize(&person)
+        .map_err(|_| (StatusCode::INTERNAL_SERVER_ERROR, "Unable to encode Person"))?;
+    Ok(([(header::CONTENT_TYPE, "application/octet-stream")], bytes))
+}
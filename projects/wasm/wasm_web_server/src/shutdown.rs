@@ -0,0 +1,46 @@
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Long-lived tasks (like WebSocket sessions) hold one of these and stop
+/// when it changes. Hyper's graceful shutdown only drains ordinary
+/// requests; upgraded connections are on their own.
+pub type ShutdownReceiver = watch::Receiver<bool>;
+
+pub fn channel() -> (watch::Sender<bool>, ShutdownReceiver) {
+    watch::channel(false)
+}
+
+/// Resolves on Ctrl+C or SIGTERM, after telling everyone holding a
+/// `ShutdownReceiver` to finish up.
+pub async fn signal(tx: &watch::Sender<bool>) {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("Unable to listen for Ctrl+C");
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Unable to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
+    }
+    tracing::info!("Shutting down");
+    let _ = tx.send(true);
+}
+
+/// Waits for every `ShutdownReceiver` to be dropped, giving up after `limit`.
+pub async fn wait_for_tasks(tx: &watch::Sender<bool>, limit: Duration) {
+    if tokio::time::timeout(limit, tx.closed()).await.is_err() {
+        tracing::warn!("Some connections didn't close in time");
+    }
+}
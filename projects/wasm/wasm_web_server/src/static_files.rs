@@ -0,0 +1,98 @@
+use axum::{
+    http::{header, HeaderValue, Request, Response, StatusCode},
+    middleware::Next,
+    response::IntoResponse,
+};
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::Path,
+};
+use tower::Layer;
+use tower_http::{
+    services::ServeDir,
+    set_header::{SetResponseHeader, SetResponseHeaderLayer},
+};
+
+// The wasm bundle and its loader aren't content-hashed, so browsers must
+// check they're still current before each use. The ETag makes that a
+// cheap 304 instead of a full download.
+const REVALIDATE: HeaderValue = HeaderValue::from_static("no-cache");
+const LONG_LIVED: HeaderValue = HeaderValue::from_static("public, max-age=86400");
+// The same URL can come back brotli, gzip or uncompressed, so caches must
+// key on Accept-Encoding too.
+const VARY_ENCODING: HeaderValue = HeaderValue::from_static("accept-encoding");
+
+fn cache_control<B>(response: &Response<B>) -> Option<HeaderValue> {
+    if !response.status().is_success() {
+        return None;
+    }
+    let content_type = response.headers().get(header::CONTENT_TYPE)?.to_str().ok()?;
+    if content_type.starts_with("application/wasm")
+        || content_type.contains("javascript")
+        || content_type.starts_with("text/html")
+    {
+        Some(REVALIDATE)
+    } else {
+        Some(LONG_LIVED)
+    }
+}
+
+type WithCacheControl = SetResponseHeader<
+    ServeDir<ServeDir>,
+    fn(&Response<tower_http::services::fs::ServeFileSystemResponseBody>) -> Option<HeaderValue>,
+>;
+type WithVary = SetResponseHeader<WithCacheControl, HeaderValue>;
+
+/// Serves the files in `build_dir`, then those in `static_dir`, with
+/// Cache-Control headers. If the client accepts it, `app.wasm` is answered
+/// with `app.wasm.br` or `app.wasm.gz` from `build_dir` when those exist (see
+/// build.rs), still as `application/wasm` but with a Content-Encoding.
+/// Otherwise the uncompressed file is sent.
+pub fn service(build_dir: &Path, static_dir: &Path) -> WithVary {
+    let files = ServeDir::new(build_dir)
+        .precompressed_br()
+        .precompressed_gzip()
+        .fallback(ServeDir::new(static_dir));
+    let files =
+        SetResponseHeaderLayer::if_not_present(header::CACHE_CONTROL, cache_control as fn(&_) -> _)
+            .layer(files);
+    SetResponseHeaderLayer::if_not_present(header::VARY, VARY_ENCODING).layer(files)
+}
+
+/// Middleware that adds a weak ETag to static files, built from their
+/// modification time and size, and answers a matching If-None-Match with 304.
+pub async fn etag<B>(request: Request<B>, next: Next<B>) -> impl IntoResponse {
+    let if_none_match = request.headers().get(header::IF_NONE_MATCH).cloned();
+    let mut response = next.run(request).await;
+
+    // ServeDir sets Last-Modified; other routes don't, so they're left alone
+    let Some(last_modified) = response.headers().get(header::LAST_MODIFIED) else {
+        return response;
+    };
+    let Some(length) = response.headers().get(header::CONTENT_LENGTH) else {
+        return response;
+    };
+
+    let mut hasher = DefaultHasher::new();
+    last_modified.as_bytes().hash(&mut hasher);
+    length.as_bytes().hash(&mut hasher);
+    let tag = format!("W/\"{:x}\"", hasher.finish());
+    let Ok(tag) = HeaderValue::from_str(&tag) else {
+        return response;
+    };
+
+    if if_none_match.as_ref() == Some(&tag) {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        for name in [header::CACHE_CONTROL, header::LAST_MODIFIED] {
+            if let Some(value) = response.headers().get(&name) {
+                not_modified.headers_mut().insert(name, value.clone());
+            }
+        }
+        not_modified.headers_mut().insert(header::ETAG, tag);
+        return not_modified;
+    }
+
+    response.headers_mut().insert(header::ETAG, tag);
+    response
+}
@@ -0,0 +1,58 @@
+use crate::shutdown::ShutdownReceiver;
+use axum::{
+    extract::ws::{close_code, CloseFrame, Message, WebSocket, WebSocketUpgrade},
+    response::IntoResponse,
+    Extension,
+};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+/// Every connected client holds a receiver; sending on this reaches all of them.
+pub type Broadcaster = broadcast::Sender<String>;
+
+pub fn broadcaster() -> Broadcaster {
+    let (tx, _rx) = broadcast::channel(100);
+    tx
+}
+
+pub async fn ws_handler(
+    ws: WebSocketUpgrade,
+    Extension(tx): Extension<Broadcaster>,
+    Extension(shutdown): Extension<ShutdownReceiver>,
+) -> impl IntoResponse {
+    ws.on_upgrade(move |socket| relay_socket(socket, tx, shutdown))
+}
+
+// Relays every text message to all connected clients, including the sender
+async fn relay_socket(mut socket: WebSocket, tx: Broadcaster, mut shutdown: ShutdownReceiver) {
+    let mut rx = tx.subscribe();
+    loop {
+        tokio::select! {
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Text(text))) => {
+                    // Only fails if nobody is listening, and we are
+                    let _ = tx.send(text);
+                }
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                _ => {}
+            },
+            outgoing = rx.recv() => match outgoing {
+                Ok(text) => {
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                // A slow client missed some messages; carry on from the newest
+                Err(RecvError::Lagged(_)) => {}
+                Err(RecvError::Closed) => break,
+            },
+            _ = shutdown.changed() => {
+                let goodbye = CloseFrame {
+                    code: close_code::AWAY,
+                    reason: "Server shutting down".into(),
+                };
+                let _ = socket.send(Message::Close(Some(goodbye))).await;
+                break;
+            }
+        }
+    }
+}
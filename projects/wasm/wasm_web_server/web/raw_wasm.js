@@ -0,0 +1,60 @@
+// A hand-written loader for wasm_raw.wasm. This is the glue that
+// wasm-bindgen generates for wasm_lib: providing the imports, and copying
+// strings and byte arrays in and out of the module's linear memory.
+
+async function loadRawWasm(url) {
+    const encoder = new TextEncoder();
+    const decoder = new TextDecoder();
+    let exports;
+
+    // Pointers are just offsets into the module's memory. Make a new view
+    // each time, because the old one is detached if memory grows.
+    const bytesAt = (ptr, len) => new Uint8Array(exports.memory.buffer, ptr, len);
+
+    const imports = {
+        env: {
+            log_number: (n) => console.log(n),
+            log_str: (ptr, len) => console.log(decoder.decode(bytesAt(ptr, len))),
+        },
+    };
+
+    const { instance } = await WebAssembly.instantiateStreaming(fetch(url), imports);
+    exports = instance.exports;
+
+    // Copies `bytes` into a fresh allocation, calls `f(ptr, len)` and frees it
+    function withBytes(bytes, f) {
+        const ptr = exports.alloc(bytes.length);
+        bytesAt(ptr, bytes.length).set(bytes);
+        try {
+            return f(ptr, bytes.length);
+        } finally {
+            exports.dealloc(ptr, bytes.length);
+        }
+    }
+
+    return {
+        hello_js: () => exports.hello_js(),
+        add: (a, b) => exports.add(a, b),
+        sum_bytes: (bytes) => withBytes(bytes, exports.sum_bytes),
+        to_uppercase: (text) =>
+            withBytes(encoder.encode(text), (ptr, len) => {
+                exports.to_uppercase(ptr, len);
+                return decoder.decode(bytesAt(ptr, len));
+            }),
+        greet: (name) =>
+            withBytes(encoder.encode(name), (namePtr, nameLen) => {
+                let capacity = 16;
+                for (;;) {
+                    const result = withBytes(new Uint8Array(capacity), (outPtr, outLen) => {
+                        const needed = exports.greet(namePtr, nameLen, outPtr, outLen);
+                        return needed <= outLen ? decoder.decode(bytesAt(outPtr, needed)) : needed;
+                    });
+                    if (typeof result === "string") {
+                        return result;
+                    }
+                    // Too small: go round again with the size it asked for
+                    capacity = result;
+                }
+            }),
+    };
+}